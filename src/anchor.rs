@@ -0,0 +1,455 @@
+use crate::decode::Fields;
+use crate::labels;
+use anyhow::Result;
+use flate2::read::ZlibDecoder;
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+use std::io::Read;
+
+/// Seed used by Anchor to derive a program's on-chain IDL account.
+const IDL_SEED: &str = "anchor:idl";
+/// IDL account header: discriminator (8) + authority (32) + data length (4).
+const IDL_HEADER_LEN: usize = 44;
+/// Elements of a vector or array shown before truncating.
+const MAX_LIST_ITEMS: usize = 16;
+/// Largest decompressed IDL accepted, guarding against zlib bombs.
+const MAX_IDL_LEN: usize = 4 * 1024 * 1024;
+/// Deepest type nesting decoded, guarding against self-referencing IDL types.
+const MAX_DEPTH: usize = 32;
+
+/// An account matched against an Anchor discriminator.
+pub struct AnchorAccount {
+    pub name: String,
+    /// Decoded fields, or `None` when the IDL type could not be decoded.
+    pub fields: Option<Fields>,
+}
+
+/// Address of the on-chain IDL account for an Anchor program.
+pub fn idl_address(program_id: &Pubkey) -> Result<Pubkey> {
+    let (base, _) = Pubkey::find_program_address(&[], program_id);
+    Ok(Pubkey::create_with_seed(&base, IDL_SEED, program_id)?)
+}
+
+/// Fetch and decompress the IDL published by an Anchor program.
+///
+/// Returns `None` when the program has no IDL account.
+pub fn fetch_idl(client: &RpcClient, program_id: &Pubkey) -> Result<Option<Value>> {
+    let address = idl_address(program_id)?;
+    let Some(account) = client
+        .get_account_with_commitment(&address, client.commitment())?
+        .value
+    else {
+        return Ok(None);
+    };
+    let data = &account.data;
+    let len = data
+        .get(40..IDL_HEADER_LEN)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()) as usize)
+        .ok_or_else(|| anyhow::anyhow!("IDL account is truncated"))?;
+    let compressed = data
+        .get(IDL_HEADER_LEN..IDL_HEADER_LEN + len)
+        .ok_or_else(|| anyhow::anyhow!("IDL account is truncated"))?;
+    let mut json = String::new();
+    ZlibDecoder::new(compressed)
+        .take(MAX_IDL_LEN as u64 + 1)
+        .read_to_string(&mut json)?;
+    if json.len() > MAX_IDL_LEN {
+        anyhow::bail!("IDL is larger than {} bytes", MAX_IDL_LEN);
+    }
+    Ok(Some(serde_json::from_str(&json)?))
+}
+
+/// The 8 byte discriminator Anchor prefixes to accounts of type `name`.
+pub fn discriminator(name: &str) -> [u8; 8] {
+    let digest = hash(format!("account:{}", name).as_bytes()).to_bytes();
+    digest[..8].try_into().unwrap()
+}
+
+/// Match an account's discriminator against its owner's IDL and decode it.
+///
+/// Returns `None` when the owner has no IDL or no account type matches.
+pub fn decode_account(client: &RpcClient, account: &Account) -> Result<Option<AnchorAccount>> {
+    let Some(prefix) = account.data.get(..8) else {
+        return Ok(None);
+    };
+    let Some(idl) = fetch_idl(client, &account.owner)? else {
+        return Ok(None);
+    };
+    Ok(match_account(&idl, prefix, &account.data[8..]))
+}
+
+/// Find the IDL account type with the given discriminator and decode `data`.
+pub fn match_account(idl: &Value, prefix: &[u8], data: &[u8]) -> Option<AnchorAccount> {
+    let accounts = idl["accounts"].as_array()?;
+    let entry = accounts.iter().find(|entry| {
+        let Some(name) = entry["name"].as_str() else {
+            return false;
+        };
+        // Anchor >= 0.30 stores the discriminator; older IDLs derive it from the name
+        match entry["discriminator"].as_array() {
+            Some(bytes) => bytes
+                .iter()
+                .map(|b| b.as_u64().unwrap_or(256))
+                .eq(prefix.iter().map(|b| *b as u64)),
+            None => discriminator(name) == prefix,
+        }
+    })?;
+    let name = entry["name"].as_str()?.to_string();
+
+    // Older IDLs inline the type; newer ones reference `types` by name
+    let ty = if entry["type"].is_object() {
+        Some(&entry["type"])
+    } else {
+        find_type(idl, &name)
+    };
+    let fields = ty.and_then(|ty| {
+        let mut decoder = Decoder {
+            idl,
+            data,
+            offset: 0,
+            depth: 0,
+        };
+        let mut fields = Fields::new();
+        decoder.defined(ty, "", &mut fields).ok()?;
+        Some(fields)
+    });
+    Some(AnchorAccount { name, fields })
+}
+
+fn find_type<'a>(idl: &'a Value, name: &str) -> Option<&'a Value> {
+    idl["types"]
+        .as_array()?
+        .iter()
+        .find(|t| t["name"].as_str() == Some(name))
+        .map(|t| &t["type"])
+}
+
+/// Borsh decoder driven by IDL type descriptions.
+struct Decoder<'a> {
+    idl: &'a Value,
+    data: &'a [u8],
+    offset: usize,
+    /// Current nesting of `value` calls.
+    depth: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + len)
+            .ok_or_else(|| anyhow::anyhow!("Account data ended early"))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn len_prefix(&mut self) -> Result<usize> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()) as usize)
+    }
+
+    /// Decode a struct or enum definition, appending flattened fields.
+    fn defined(&mut self, ty: &Value, path: &str, out: &mut Fields) -> Result<()> {
+        match ty["kind"].as_str() {
+            Some("struct") => {
+                let fields = ty["fields"].as_array().cloned().unwrap_or_default();
+                for (i, f) in fields.iter().enumerate() {
+                    // Tuple structs list bare types instead of named fields
+                    let (name, field_ty) = match f["name"].as_str() {
+                        Some(name) => (name.to_string(), &f["type"]),
+                        None => (i.to_string(), f),
+                    };
+                    self.value(field_ty, &join(path, &name), out)?;
+                }
+                Ok(())
+            }
+            Some("enum") => {
+                let variant = self.take(1)?[0] as usize;
+                let variants = ty["variants"].as_array().cloned().unwrap_or_default();
+                let v = variants
+                    .get(variant)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown enum variant {}", variant))?;
+                let name = v["name"].as_str().unwrap_or("?");
+                out.push((path.to_string(), name.to_string()));
+                if let Some(fields) = v.get("fields") {
+                    let variant_ty = serde_json::json!({ "kind": "struct", "fields": fields });
+                    self.defined(&variant_ty, &join(path, name), out)?;
+                }
+                Ok(())
+            }
+            _ => anyhow::bail!("Unsupported IDL type definition"),
+        }
+    }
+
+    /// Decode a value of any IDL type at `path`.
+    fn value(&mut self, ty: &Value, path: &str, out: &mut Fields) -> Result<()> {
+        if self.depth >= MAX_DEPTH {
+            anyhow::bail!("IDL types nest deeper than {} levels", MAX_DEPTH);
+        }
+        self.depth += 1;
+        let result = self.nested(ty, path, out);
+        self.depth -= 1;
+        result
+    }
+
+    fn nested(&mut self, ty: &Value, path: &str, out: &mut Fields) -> Result<()> {
+        if let Some(prim) = ty.as_str() {
+            let value = self.primitive(prim)?;
+            out.push((path.to_string(), value));
+            return Ok(());
+        }
+        if let Some(inner) = ty.get("option").or_else(|| ty.get("coption")) {
+            let present = if ty.get("coption").is_some() {
+                u32::from_le_bytes(self.take(4)?.try_into().unwrap()) != 0
+            } else {
+                self.take(1)?[0] != 0
+            };
+            if present {
+                return self.value(inner, path, out);
+            }
+            out.push((path.to_string(), "None".to_string()));
+            return Ok(());
+        }
+        if let Some(inner) = ty.get("vec") {
+            let len = self.len_prefix()?;
+            return self.list(inner, len, path, out);
+        }
+        if let Some(array) = ty.get("array").and_then(Value::as_array) {
+            let len = array.get(1).and_then(Value::as_u64).unwrap_or(0) as usize;
+            let inner = array
+                .first()
+                .ok_or_else(|| anyhow::anyhow!("Malformed array type"))?;
+            return self.list(inner, len, path, out);
+        }
+        if let Some(defined) = ty.get("defined") {
+            let name = defined
+                .as_str()
+                .or_else(|| defined["name"].as_str())
+                .ok_or_else(|| anyhow::anyhow!("Malformed defined type"))?;
+            let def = find_type(self.idl, name)
+                .ok_or_else(|| anyhow::anyhow!("Type {} not found in IDL", name))?;
+            return self.defined(def, path, out);
+        }
+        anyhow::bail!("Unsupported IDL type {}", ty)
+    }
+
+    /// Decode `len` elements; primitive lists are shown inline.
+    fn list(&mut self, inner: &Value, len: usize, path: &str, out: &mut Fields) -> Result<()> {
+        // Bound the loop even for zero-sized elements
+        if len > self.data.len() - self.offset {
+            anyhow::bail!("Account data ended early");
+        }
+        if let Some(prim) = inner.as_str() {
+            if prim == "u8" {
+                let bytes = self.take(len)?;
+                out.push((path.to_string(), format!("0x{}", hex(bytes))));
+                return Ok(());
+            }
+            let mut items = Vec::new();
+            for _ in 0..len {
+                items.push(self.primitive(prim)?);
+            }
+            let shown = items.len().min(MAX_LIST_ITEMS);
+            let mut text = items[..shown].join(", ");
+            if items.len() > shown {
+                text.push_str(&format!(", … ({} total)", items.len()));
+            }
+            out.push((path.to_string(), format!("[{}]", text)));
+            return Ok(());
+        }
+        for i in 0..len {
+            self.value(inner, &format!("{}[{}]", path, i), out)?;
+        }
+        Ok(())
+    }
+
+    fn primitive(&mut self, ty: &str) -> Result<String> {
+        macro_rules! int {
+            ($t:ty) => {{
+                let size = std::mem::size_of::<$t>();
+                <$t>::from_le_bytes(self.take(size)?.try_into().unwrap()).to_string()
+            }};
+        }
+        Ok(match ty {
+            "bool" => (self.take(1)?[0] != 0).to_string(),
+            "u8" => int!(u8),
+            "i8" => int!(i8),
+            "u16" => int!(u16),
+            "i16" => int!(i16),
+            "u32" => int!(u32),
+            "i32" => int!(i32),
+            "u64" => int!(u64),
+            "i64" => int!(i64),
+            "u128" => int!(u128),
+            "i128" => int!(i128),
+            "f32" => int!(f32),
+            "f64" => int!(f64),
+            "publicKey" | "pubkey" => {
+                let key = Pubkey::try_from(self.take(32)?)
+                    .map_err(|_| anyhow::anyhow!("Invalid pubkey bytes"))?;
+                labels::display(&key.to_string())
+            }
+            "string" => {
+                let len = self.len_prefix()?;
+                String::from_utf8_lossy(self.take(len)?).into_owned()
+            }
+            "bytes" => {
+                let len = self.len_prefix()?;
+                format!("0x{}", hex(self.take(len)?))
+            }
+            other => anyhow::bail!("Unsupported primitive type {}", other),
+        })
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn idl() -> Value {
+        json!({
+            "accounts": [
+                { "name": "Pool", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8] },
+                { "name": "Vault" },
+                { "name": "Node" },
+                { "name": "Empties" }
+            ],
+            "types": [
+                {
+                    "name": "Pool",
+                    "type": { "kind": "struct", "fields": [{ "name": "fee", "type": "u16" }] }
+                },
+                {
+                    "name": "Vault",
+                    "type": {
+                        "kind": "struct",
+                        "fields": [
+                            { "name": "amount", "type": "u64" },
+                            { "name": "bump", "type": { "option": "u8" } },
+                            { "name": "limit", "type": { "coption": "u32" } },
+                            { "name": "tags", "type": { "vec": "u16" } },
+                            { "name": "seed", "type": { "array": ["u8", 4] } },
+                            { "name": "state", "type": { "defined": { "name": "State" } } }
+                        ]
+                    }
+                },
+                {
+                    "name": "State",
+                    "type": {
+                        "kind": "enum",
+                        "variants": [
+                            { "name": "Idle" },
+                            { "name": "Locked", "fields": [{ "name": "until", "type": "i64" }] }
+                        ]
+                    }
+                },
+                {
+                    "name": "Node",
+                    "type": {
+                        "kind": "struct",
+                        "fields": [{ "name": "next", "type": { "option": { "defined": "Node" } } }]
+                    }
+                },
+                {
+                    "name": "Empties",
+                    "type": {
+                        "kind": "struct",
+                        "fields": [{ "name": "items", "type": { "vec": { "defined": "Empty" } } }]
+                    }
+                },
+                { "name": "Empty", "type": { "kind": "struct", "fields": [] } }
+            ]
+        })
+    }
+
+    #[test]
+    fn discriminator_is_sha256_prefix() {
+        let digest = hash(b"account:Vault").to_bytes();
+        assert_eq!(discriminator("Vault"), digest[..8]);
+        assert_ne!(discriminator("Vault"), discriminator("vault"));
+    }
+
+    #[test]
+    fn matches_explicit_discriminator() {
+        let account = match_account(&idl(), &[1, 2, 3, 4, 5, 6, 7, 8], &[0x10, 0x27]).unwrap();
+        assert_eq!(account.name, "Pool");
+        assert_eq!(
+            account.fields.unwrap(),
+            [("fee".to_string(), "10000".to_string())]
+        );
+        assert!(match_account(&idl(), &[0; 8], &[]).is_none());
+    }
+
+    #[test]
+    fn decodes_nested_types() {
+        let mut data = Vec::new();
+        data.extend(500u64.to_le_bytes());
+        data.extend([1, 254]); // bump: Some(254)
+        data.extend(0u32.to_le_bytes()); // limit: None
+        data.extend(2u32.to_le_bytes());
+        data.extend(7u16.to_le_bytes());
+        data.extend(9u16.to_le_bytes());
+        data.extend([0xde, 0xad, 0xbe, 0xef]);
+        data.push(1); // Locked
+        data.extend((-5i64).to_le_bytes());
+
+        let account = match_account(&idl(), &discriminator("Vault"), &data).unwrap();
+        assert_eq!(account.name, "Vault");
+        let fields = account.fields.unwrap();
+        let expected = [
+            ("amount", "500"),
+            ("bump", "254"),
+            ("limit", "None"),
+            ("tags", "[7, 9]"),
+            ("seed", "0xdeadbeef"),
+            ("state", "Locked"),
+            ("state.Locked.until", "-5"),
+        ];
+        assert_eq!(fields.len(), expected.len());
+        for ((name, value), (want_name, want_value)) in fields.iter().zip(expected) {
+            assert_eq!(name, want_name);
+            assert_eq!(value, want_value);
+        }
+    }
+
+    #[test]
+    fn truncated_data_leaves_fields_undecoded() {
+        let account = match_account(&idl(), &discriminator("Vault"), &[0; 4]).unwrap();
+        assert_eq!(account.name, "Vault");
+        assert!(account.fields.is_none());
+    }
+
+    #[test]
+    fn self_referencing_type_stops_at_depth_limit() {
+        let data = vec![1; 1024];
+        let account = match_account(&idl(), &discriminator("Node"), &data).unwrap();
+        assert!(account.fields.is_none());
+    }
+
+    #[test]
+    fn zero_sized_elements_cannot_exceed_remaining_bytes() {
+        let data = u32::MAX.to_le_bytes();
+        let account = match_account(&idl(), &discriminator("Empties"), &data).unwrap();
+        assert!(account.fields.is_none());
+
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend([0; 3]);
+        let account = match_account(&idl(), &discriminator("Empties"), &data).unwrap();
+        assert!(account.fields.is_some());
+    }
+}
@@ -1,19 +1,49 @@
+use crate::anchor;
+use crate::decode;
+use crate::labels;
+use crate::utils;
 use anyhow::Result;
+use base64::prelude::*;
 use colored::*;
-use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
 use solana_client::rpc_request::TokenAccountsFilter;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::io::Write;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-pub fn get_info(client: &RpcClient, address_str: &str) -> Result<()> {
+/// Fetch and display account information and decode its data.
+///
+/// Data is decoded according to the owner program (token, stake, vote,
+/// nonce, lookup table, loader, config and sysvar accounts); other
+/// accounts are matched against the owner's Anchor IDL by discriminator.
+/// With `--data` the raw bytes are dumped instead of decoded.
+pub fn get_info(client: &RpcClient, address_str: &str, dump: &DataArgs) -> Result<()> {
     let pubkey = Pubkey::from_str(address_str)
         .map_err(|_| anyhow::anyhow!("Invalid Solana address format"))?;
 
     let account = client.get_account(&pubkey)?;
     let balance = client.get_balance(&pubkey)?;
 
+    let slice = dump.slice(&account.data)?;
+    if let Some(path) = &dump.out {
+        std::fs::write(path, slice)?;
+    }
+    // Raw bytes go to stdout untouched so they can be piped
+    if dump.data == Some(DataFormat::Raw) {
+        std::io::stdout().write_all(slice)?;
+        return Ok(());
+    }
+
     println!("\n{}", "--- Account Information ---".bold().cyan());
     println!("{:<20} : {}", "Address", address_str.yellow());
+    if let Some(label) = labels::lookup(address_str) {
+        println!("{:<20} : {}", "Label", label.cyan());
+    }
     println!(
         "{:<20} : {:.9} SOL",
         "Balance",
@@ -26,7 +56,7 @@ pub fn get_info(client: &RpcClient, address_str: &str) -> Result<()> {
     println!(
         "{:<20} : {}",
         "Owner Program",
-        account.owner.to_string().magenta()
+        labels::display(&account.owner.to_string()).magenta()
     );
     println!(
         "{:<20} : {}",
@@ -38,24 +68,311 @@ pub fn get_info(client: &RpcClient, address_str: &str) -> Result<()> {
         }
     );
     println!("{:<20} : {} bytes", "Data Size", account.data.len());
+    if let Some(path) = &dump.out {
+        println!(
+            "{:<20} : {} bytes to {}",
+            "Saved",
+            slice.len(),
+            path.display().to_string().green()
+        );
+    }
+
+    if let Some(format) = dump.data {
+        print_data(format, dump.offset, slice);
+        return Ok(());
+    }
+    if account.data.is_empty() {
+        return Ok(());
+    }
+    match decode::decode(&pubkey, &account) {
+        Some((layout, Ok(fields))) => {
+            println!(
+                "\n{}",
+                format!("--- Decoded Data ({}) ---", layout).bold().cyan()
+            );
+            decode::print_fields(&fields);
+        }
+        Some((layout, Err(e))) => {
+            println!("\n{} {}: {}", "Could not decode as".yellow(), layout, e);
+        }
+        None => print_anchor_data(client, &account)?,
+    }
     Ok(())
 }
 
-pub fn get_history(client: &RpcClient, address_str: &str) -> Result<()> {
-    let pubkey = Pubkey::from_str(address_str).map_err(|_| anyhow::anyhow!("Invalid Address"))?;
-    // Fetch last 20 signatures
-    let history = client.get_signatures_for_address(&pubkey)?;
+/// Fall back to the owner's Anchor IDL to identify and decode the account.
+fn print_anchor_data(client: &RpcClient, account: &Account) -> Result<()> {
+    let Some(prefix) = account.data.get(..8) else {
+        return Ok(());
+    };
+    match anchor::decode_account(client, account) {
+        Ok(Some(decoded)) => {
+            println!(
+                "\n{}",
+                format!("--- Decoded Data (Anchor: {}) ---", decoded.name)
+                    .bold()
+                    .cyan()
+            );
+            match decoded.fields {
+                Some(fields) => decode::print_fields(&fields),
+                None => println!(
+                    "{}",
+                    "Account type matched, but its layout could not be decoded.".yellow()
+                ),
+            }
+        }
+        result => {
+            let layout = match result {
+                Err(e) => format!("Unknown (Anchor IDL lookup failed: {})", e),
+                _ => "Unknown (no built-in decoder or Anchor IDL match)".to_string(),
+            };
+            println!("\n{}", "--- Data ---".bold().cyan());
+            println!("{:<25} : {}", "Layout", layout.yellow());
+            println!("{:<25} : {}", "Discriminator", anchor::hex(prefix));
+        }
+    }
+    Ok(())
+}
+
+/// Encodings for dumping raw account data.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DataFormat {
+    /// Annotated hexdump (offset, hex bytes, ASCII).
+    Hex,
+    Base64,
+    Base58,
+    /// Unformatted bytes on stdout, for piping.
+    Raw,
+}
+
+/// Command-line options for dumping account data in `account-info`.
+#[derive(clap::Args, Default)]
+pub struct DataArgs {
+    /// Dump the account data in this encoding instead of decoding it.
+    #[arg(long, value_enum)]
+    pub data: Option<DataFormat>,
+
+    /// Start of the dumped byte range.
+    #[arg(long, default_value_t = 0)]
+    pub offset: usize,
+
+    /// Number of bytes to dump (defaults to the rest of the data).
+    #[arg(long)]
+    pub length: Option<usize>,
+
+    /// Save the selected bytes to this file.
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+}
+
+impl DataArgs {
+    /// The byte range selected by `--offset` and `--length`.
+    fn slice<'a>(&self, data: &'a [u8]) -> Result<&'a [u8]> {
+        if self.offset > data.len() {
+            anyhow::bail!(
+                "Offset {} is past the end of the account data ({} bytes)",
+                self.offset,
+                data.len()
+            );
+        }
+        let end = match self.length {
+            Some(len) => self.offset.saturating_add(len).min(data.len()),
+            None => data.len(),
+        };
+        Ok(&data[self.offset..end])
+    }
+}
 
+/// Print a slice of account data starting at `offset` in the given format.
+fn print_data(format: DataFormat, offset: usize, bytes: &[u8]) {
     println!(
         "\n{}",
         format!(
-            "--- Transaction History (Last {}) ---",
-            history.len().min(20)
+            "--- Account Data [{}..{}] ({} bytes) ---",
+            offset,
+            offset + bytes.len(),
+            bytes.len()
         )
         .bold()
         .cyan()
     );
-    for (i, sig_info) in history.iter().take(20).enumerate() {
+    match format {
+        DataFormat::Hex => print!("{}", utils::hexdump(bytes, offset)),
+        DataFormat::Base64 => println!("{}", BASE64_STANDARD.encode(bytes)),
+        DataFormat::Base58 => println!("{}", bs58::encode(bytes).into_string()),
+        DataFormat::Raw => unreachable!("raw data is written before the summary"),
+    }
+}
+
+/// Command-line options for `account-history`.
+#[derive(clap::Args)]
+pub struct HistoryArgs {
+    /// Maximum number of signatures to list (0 for the full history).
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Start searching backwards from this transaction signature.
+    #[arg(long)]
+    pub before: Option<String>,
+
+    /// Stop once this transaction signature is reached.
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Only include transactions at or after this date (YYYY-MM-DD or RFC3339).
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Only include transactions at or before this date (YYYY-MM-DD or RFC3339).
+    #[arg(long = "until-date")]
+    pub until_date: Option<String>,
+
+    /// Only list failed transactions.
+    #[arg(long, conflicts_with = "success")]
+    pub failed: bool,
+
+    /// Only list successful transactions.
+    #[arg(long)]
+    pub success: bool,
+}
+
+/// Filters applied while paging through an address's signature history.
+#[derive(Default)]
+pub struct HistoryFilter {
+    /// Stop after this many matching signatures (`None` walks the full history).
+    pub limit: Option<usize>,
+    pub before: Option<Signature>,
+    pub until: Option<Signature>,
+    /// Inclusive lower bound on block time (unix seconds).
+    pub since_time: Option<i64>,
+    /// Inclusive upper bound on block time (unix seconds).
+    pub until_time: Option<i64>,
+    /// `Some(true)` keeps only failed transactions, `Some(false)` only successful ones.
+    pub failed: Option<bool>,
+}
+
+impl HistoryArgs {
+    pub fn to_filter(&self) -> Result<HistoryFilter> {
+        let parse_sig = |s: &Option<String>| -> Result<Option<Signature>> {
+            s.as_deref()
+                .map(|s| {
+                    Signature::from_str(s).map_err(|_| anyhow::anyhow!("Invalid Signature: {}", s))
+                })
+                .transpose()
+        };
+        Ok(HistoryFilter {
+            limit: (self.limit > 0).then_some(self.limit),
+            before: parse_sig(&self.before)?,
+            until: parse_sig(&self.until)?,
+            since_time: self
+                .since
+                .as_deref()
+                .map(|d| utils::parse_date(d, false))
+                .transpose()?,
+            until_time: self
+                .until_date
+                .as_deref()
+                .map(|d| utils::parse_date(d, true))
+                .transpose()?,
+            failed: match (self.failed, self.success) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+        })
+    }
+}
+
+/// Page size used by `getSignaturesForAddress` (the RPC maximum).
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+
+/// Walk the signature history of an address, newest first, applying `filter`.
+///
+/// Pages through `getSignaturesForAddress` until the limit is reached, the
+/// `since` date is passed or the history is exhausted. Progress is reported
+/// on stderr once more than one page is needed.
+pub fn fetch_history(
+    client: &RpcClient,
+    pubkey: &Pubkey,
+    filter: &HistoryFilter,
+) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+    let mut results = Vec::new();
+    let mut before = filter.before;
+    let mut scanned = 0usize;
+    let mut pages = 0usize;
+
+    'pages: loop {
+        let page = client.get_signatures_for_address_with_config(
+            pubkey,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: filter.until,
+                limit: Some(SIGNATURE_PAGE_SIZE),
+                commitment: Some(CommitmentConfig::finalized()),
+            },
+        )?;
+        pages += 1;
+        scanned += page.len();
+
+        for sig_info in &page {
+            if let Some(t) = sig_info.block_time {
+                if filter.until_time.is_some_and(|until| t > until) {
+                    continue;
+                }
+                // History is returned newest first, so nothing older can match.
+                if filter.since_time.is_some_and(|since| t < since) {
+                    break 'pages;
+                }
+            }
+            if let Some(failed) = filter.failed
+                && sig_info.err.is_some() != failed
+            {
+                continue;
+            }
+            results.push(sig_info.clone());
+            if filter.limit.is_some_and(|limit| results.len() >= limit) {
+                break 'pages;
+            }
+        }
+
+        if pages > 1 {
+            eprint!(
+                "\rScanned {} signatures, {} matching...",
+                scanned,
+                results.len()
+            );
+        }
+
+        match page.last() {
+            Some(last) if page.len() == SIGNATURE_PAGE_SIZE => {
+                before = Some(Signature::from_str(&last.signature)?);
+            }
+            _ => break,
+        }
+    }
+
+    if pages > 1 {
+        eprintln!();
+    }
+    Ok(results)
+}
+
+pub fn get_history(client: &RpcClient, address_str: &str, args: &HistoryArgs) -> Result<()> {
+    let pubkey = Pubkey::from_str(address_str).map_err(|_| anyhow::anyhow!("Invalid Address"))?;
+    let filter = args.to_filter()?;
+    let history = fetch_history(client, &pubkey, &filter)?;
+
+    println!(
+        "\n{}",
+        format!("--- Transaction History ({}) ---", history.len())
+            .bold()
+            .cyan()
+    );
+    if history.is_empty() {
+        println!("No matching transactions found.");
+        return Ok(());
+    }
+    for (i, sig_info) in history.iter().enumerate() {
         let status = if sig_info.err.is_none() {
             "SUCCESS".green()
         } else {
@@ -64,17 +381,18 @@ pub fn get_history(client: &RpcClient, address_str: &str) -> Result<()> {
         let date = if let Some(t) = sig_info.block_time {
             chrono::DateTime::from_timestamp(t, 0)
                 .unwrap_or_default()
-                .format("%Y-%m-%d")
+                .format("%Y-%m-%d %H:%M:%S")
                 .to_string()
         } else {
             "N/A".to_string()
         };
         println!(
-            "[{:02}] {} | {} | Slot: {}",
+            "[{:02}] {} | {} | Slot: {} | {}",
             i,
             sig_info.signature.yellow(),
             status,
-            sig_info.slot
+            sig_info.slot,
+            date
         );
     }
     Ok(())
@@ -97,7 +415,7 @@ pub fn get_tokens(client: &RpcClient, address_str: &str) -> Result<()> {
             .cyan()
     );
     for act in &accounts {
-        println!("Account: {}", act.pubkey);
+        println!("Account: {}", labels::display(&act.pubkey));
         // We skip deep parsing for speed, or user uses `token` command
     }
     // Consider using get_parsed for 'Tokens' command in Account as requested "must list every SPL Token mint"
@@ -121,3 +439,23 @@ pub fn get_stake(client: &RpcClient, address_str: &str) -> Result<()> {
     }
     Ok(())
 }
+
+/// Decode a durable nonce account (Authority, Nonce, Fee).
+pub fn get_nonce(client: &RpcClient, address_str: &str) -> Result<()> {
+    let pubkey = Pubkey::from_str(address_str).map_err(|_| anyhow::anyhow!("Invalid Address"))?;
+    let account = client.get_account(&pubkey)?;
+    if decode::kind(&account) != decode::AccountKind::Nonce {
+        anyhow::bail!("Account is not a nonce account");
+    }
+    let fields = decode::nonce_account(&account.data)?;
+
+    println!("\n{}", "--- Nonce Account ---".bold().cyan());
+    println!("{:<25} : {}", "Address", address_str.yellow());
+    println!(
+        "{:<25} : {:.9} SOL",
+        "Balance",
+        account.lamports as f64 / 1_000_000_000.0
+    );
+    decode::print_fields(&fields);
+    Ok(())
+}
@@ -0,0 +1,440 @@
+use crate::anchor;
+use crate::decode::{self, Fields};
+use crate::labels;
+use crate::rpc;
+use crate::utils;
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use serde_json::Value;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A decoded field whose value differs between two account states.
+#[derive(Serialize)]
+struct FieldChange {
+    field: String,
+    /// `None` when the field was added.
+    old: Option<String>,
+    /// `None` when the field was removed.
+    new: Option<String>,
+}
+
+/// One account update, as appended to the NDJSON file.
+#[derive(Serialize)]
+struct ChangeRecord {
+    timestamp: String,
+    slot: u64,
+    lamports: u64,
+    lamport_delta: i128,
+    owner: String,
+    data_len: usize,
+    /// Name of the decoded layout, if any.
+    layout: Option<String>,
+    /// Bytes that differ when neither state could be decoded.
+    bytes_changed: usize,
+    changes: Vec<FieldChange>,
+}
+
+/// Decodes account states, caching the Anchor IDL of each owner.
+struct Decoder<'a> {
+    client: &'a RpcClient,
+    idls: HashMap<Pubkey, Option<Value>>,
+}
+
+impl Decoder<'_> {
+    /// The layout name and fields of `account`, or `None` if it is not decodable.
+    fn decode(&mut self, address: &Pubkey, account: &Account) -> Option<(String, Fields)> {
+        if account.data.is_empty() {
+            return None;
+        }
+        if let Some((layout, fields)) = decode::decode(address, account) {
+            return fields.ok().map(|f| (layout.to_string(), f));
+        }
+        let prefix = account.data.get(..8)?;
+        let client = self.client;
+        let idl = self
+            .idls
+            .entry(account.owner)
+            .or_insert_with(|| anchor::fetch_idl(client, &account.owner).ok().flatten())
+            .as_ref()?;
+        let decoded = anchor::match_account(idl, prefix, &account.data[8..])?;
+        Some((format!("Anchor: {}", decoded.name), decoded.fields?))
+    }
+}
+
+/// Fields that were changed, added or removed between two decodings.
+fn diff_fields(old: &Fields, new: &Fields) -> Vec<FieldChange> {
+    let before: HashMap<&str, &str> = old.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    let after: HashMap<&str, &str> = new.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    let mut changes: Vec<FieldChange> = new
+        .iter()
+        .filter(|(name, value)| before.get(name.as_str()) != Some(&value.as_str()))
+        .map(|(name, value)| FieldChange {
+            field: name.clone(),
+            old: before.get(name.as_str()).map(|v| v.to_string()),
+            new: Some(value.clone()),
+        })
+        .collect();
+    changes.extend(
+        old.iter()
+            .filter(|(name, _)| !after.contains_key(name.as_str()))
+            .map(|(name, value)| FieldChange {
+                field: name.clone(),
+                old: Some(value.clone()),
+                new: None,
+            }),
+    );
+    changes
+}
+
+/// An account state together with its decoded layout and fields.
+struct State {
+    account: Account,
+    decoded: Option<(String, Fields)>,
+}
+
+/// The change from `previous` to `current`, observed at `slot`.
+fn change_record(previous: &State, current: &State, slot: u64) -> ChangeRecord {
+    let (old, new) = (&previous.account, &current.account);
+    let empty = Fields::new();
+    let changes = diff_fields(
+        previous.decoded.as_ref().map_or(&empty, |(_, f)| f),
+        current.decoded.as_ref().map_or(&empty, |(_, f)| f),
+    );
+    let bytes_changed = if previous.decoded.is_none() && current.decoded.is_none() {
+        let common = old.data.len().min(new.data.len());
+        (0..common).filter(|&i| old.data[i] != new.data[i]).count()
+            + old.data.len().abs_diff(new.data.len())
+    } else {
+        0
+    };
+    ChangeRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        slot,
+        lamports: new.lamports,
+        lamport_delta: new.lamports as i128 - old.lamports as i128,
+        owner: new.owner.to_string(),
+        data_len: new.data.len(),
+        layout: current.decoded.as_ref().map(|(layout, _)| layout.clone()),
+        bytes_changed,
+        changes,
+    }
+}
+
+fn print_change(record: &ChangeRecord, previous: &Account) {
+    let delta = format!("{} SOL", utils::format_sol(record.lamport_delta));
+    let delta = match record.lamport_delta {
+        d if d > 0 => format!("+{}", delta).green(),
+        d if d < 0 => delta.red(),
+        _ => delta.normal(),
+    };
+    println!(
+        "\n[{}] slot {}  lamports {}  ({})",
+        chrono::Utc::now().format("%H:%M:%S"),
+        record.slot.to_string().cyan(),
+        record.lamports,
+        delta
+    );
+    let previous_owner = previous.owner.to_string();
+    if record.owner != previous_owner {
+        println!(
+            "  {:<23} : {} → {}",
+            "owner",
+            labels::display(&previous_owner),
+            labels::display(&record.owner).magenta()
+        );
+    }
+    if record.data_len != previous.data.len() {
+        println!(
+            "  {:<23} : {} → {} bytes",
+            "data size",
+            previous.data.len(),
+            record.data_len
+        );
+    }
+    for change in &record.changes {
+        match (&change.old, &change.new) {
+            (Some(old), Some(new)) => {
+                println!("  {:<23} : {} → {}", change.field, old.red(), new.green())
+            }
+            (None, Some(new)) => println!("{} {:<23} : {}", "+".green(), change.field, new),
+            (Some(old), None) => println!("{} {:<23} : {}", "-".red(), change.field, old),
+            (None, None) => {}
+        }
+    }
+    if record.bytes_changed > 0 {
+        println!("  {:<23} : {} bytes changed", "data", record.bytes_changed);
+    }
+}
+
+/// Handle one account update: decode it, print the diff against `previous`
+/// and append the change to `out` as one JSON line. Returns the new state.
+fn apply_update(
+    decoder: &mut Decoder,
+    address: &Pubkey,
+    previous: &State,
+    account: Account,
+    slot: u64,
+    out: Option<&mut dyn Write>,
+) -> Result<State> {
+    let current = State {
+        decoded: decoder.decode(address, &account),
+        account,
+    };
+    let record = change_record(previous, &current, slot);
+    print_change(&record, &previous.account);
+    if let Some(out) = out {
+        serde_json::to_writer(&mut *out, &record)?;
+        writeln!(out)?;
+        out.flush()?;
+    }
+    Ok(current)
+}
+
+/// Stream changes to an account over an `accountSubscribe` websocket.
+///
+/// Each update prints its slot, the lamport delta and a diff of the decoded
+/// fields (token accounts, mints, stake and other built-in layouts, or the
+/// owner's Anchor IDL); undecodable data reports how many bytes changed.
+/// With `out`, every update is also appended to the file as one JSON line.
+/// Stops after `count` updates when given.
+pub fn watch(
+    rpc_url: &str,
+    address_str: &str,
+    out: Option<&Path>,
+    count: Option<usize>,
+) -> Result<()> {
+    let pubkey = Pubkey::from_str(address_str)
+        .map_err(|_| anyhow::anyhow!("Invalid Solana address format"))?;
+    let client = rpc::get_client(rpc_url);
+    let commitment = CommitmentConfig::confirmed();
+    let mut decoder = Decoder {
+        client: &client,
+        idls: HashMap::new(),
+    };
+
+    let account = client
+        .get_account_with_commitment(&pubkey, commitment)?
+        .value
+        .unwrap_or_default();
+    let mut state = State {
+        decoded: decoder.decode(&pubkey, &account),
+        account,
+    };
+
+    let ws_url = rpc::ws_url(rpc_url);
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(commitment),
+        ..Default::default()
+    };
+    let (subscription, receiver) = PubsubClient::account_subscribe(&ws_url, &pubkey, Some(config))
+        .map_err(|e| anyhow::anyhow!("Failed to subscribe via {}: {}", ws_url, e))?;
+
+    let mut file = out
+        .map(|path| OpenOptions::new().create(true).append(true).open(path))
+        .transpose()?;
+
+    println!("\n{}", "--- Watching Account ---".bold().cyan());
+    println!(
+        "{:<25} : {}",
+        "Address",
+        labels::display(address_str).yellow()
+    );
+    println!("{:<25} : {}", "WebSocket", ws_url);
+    println!("{:<25} : {} lamports", "Lamports", state.account.lamports);
+    println!(
+        "{:<25} : {}",
+        "Owner Program",
+        labels::display(&state.account.owner.to_string()).magenta()
+    );
+    if let Some((layout, _)) = &state.decoded {
+        println!("{:<25} : {}", "Layout", layout);
+    }
+    if let Some(path) = out {
+        println!("{:<25} : {}", "Appending To", path.display());
+    }
+    println!("{}", "Waiting for changes (Ctrl+C to stop)...".dimmed());
+
+    let mut seen = 0usize;
+    while count.is_none_or(|n| seen < n) {
+        let Ok(update) = receiver.recv() else {
+            rpc::close_subscription(subscription);
+            anyhow::bail!("WebSocket connection closed");
+        };
+        // Closed accounts arrive as empty System-owned accounts
+        let account: Account = update.value.decode().unwrap_or_default();
+        let out = file.as_mut().map(|f| f as &mut dyn Write);
+        state = apply_update(
+            &mut decoder,
+            &pubkey,
+            &state,
+            account,
+            update.context.slot,
+            out,
+        )?;
+        seen += 1;
+    }
+    rpc::close_subscription(subscription);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::signer::keypair::Keypair;
+    use solana_sdk::system_transaction;
+    use std::time::Duration;
+
+    fn fields(pairs: &[(&str, &str)]) -> Fields {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn account(lamports: u64, data: &[u8]) -> Account {
+        Account {
+            lamports,
+            data: data.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diff_fields_reports_changed_added_and_removed() {
+        let old = fields(&[("amount", "1"), ("owner", "A"), ("closed", "no")]);
+        let new = fields(&[("amount", "2"), ("owner", "A"), ("delegate", "B")]);
+        let changes = diff_fields(&old, &new);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.field.as_str(), c.old.as_deref(), c.new.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("amount", Some("1"), Some("2")),
+                ("delegate", None, Some("B")),
+                ("closed", Some("no"), None),
+            ]
+        );
+        assert!(diff_fields(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn change_record_counts_bytes_of_undecoded_data() {
+        let previous = State {
+            account: account(10, &[1, 2, 3, 4]),
+            decoded: None,
+        };
+        let current = State {
+            account: account(7, &[1, 9, 3, 4, 5, 6]),
+            decoded: None,
+        };
+        let record = change_record(&previous, &current, 42);
+        assert_eq!(record.slot, 42);
+        assert_eq!(record.lamports, 7);
+        assert_eq!(record.lamport_delta, -3);
+        assert_eq!(record.data_len, 6);
+        assert_eq!(record.bytes_changed, 3);
+        assert!(record.changes.is_empty());
+
+        // Decoded states are compared field by field instead
+        let decoded =
+            |amount: &str| Some(("Token Account".to_string(), fields(&[("amount", amount)])));
+        let previous = State {
+            decoded: decoded("1"),
+            ..previous
+        };
+        let current = State {
+            decoded: decoded("5"),
+            ..current
+        };
+        let record = change_record(&previous, &current, 43);
+        assert_eq!(record.bytes_changed, 0);
+        assert_eq!(record.layout.as_deref(), Some("Token Account"));
+        assert_eq!(record.changes.len(), 1);
+    }
+
+    #[test]
+    fn apply_update_appends_one_json_line() {
+        // Empty accounts never need the RPC client to decode
+        let client = RpcClient::new("http://127.0.0.1:1".to_string());
+        let mut decoder = Decoder {
+            client: &client,
+            idls: HashMap::new(),
+        };
+        let previous = State {
+            account: account(0, &[]),
+            decoded: None,
+        };
+        let mut out = Vec::new();
+        let state = apply_update(
+            &mut decoder,
+            &Pubkey::new_unique(),
+            &previous,
+            account(5_000, &[]),
+            7,
+            Some(&mut out),
+        )
+        .unwrap();
+        assert_eq!(state.account.lamports, 5_000);
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 1);
+        let record: Value = serde_json::from_str(text.trim_end()).unwrap();
+        assert_eq!(record["slot"], 7);
+        assert_eq!(record["lamports"], 5_000);
+        assert_eq!(record["lamport_delta"], 5_000);
+        assert_eq!(record["changes"], Value::Array(Vec::new()));
+    }
+
+    /// Needs `solana-test-validator` on the default local ports.
+    #[test]
+    #[ignore]
+    fn watch_records_transfer_on_test_validator() {
+        let url = "http://127.0.0.1:8899";
+        let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+        let payer = Keypair::new();
+        let target = Pubkey::new_unique();
+        let path = std::env::temp_dir().join(format!("account-watch-{}.ndjson", target));
+
+        let airdrop = client
+            .request_airdrop(&payer.pubkey(), 1_000_000_000)
+            .unwrap();
+        client.poll_for_signature(&airdrop).unwrap();
+
+        let watcher = {
+            let (target, path) = (target.to_string(), path.clone());
+            std::thread::spawn(move || watch(url, &target, Some(&path), Some(1)))
+        };
+        // Give the subscription time to register before the transfer lands
+        std::thread::sleep(Duration::from_secs(2));
+        let blockhash = client.get_latest_blockhash().unwrap();
+        let transfer = system_transaction::transfer(&payer, &target, 1_000_000, blockhash);
+        client.send_and_confirm_transaction(&transfer).unwrap();
+        watcher.join().unwrap().unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let record: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(record["lamports"], 1_000_000);
+        assert_eq!(record["lamport_delta"], 1_000_000);
+        assert_eq!(record["owner"], "11111111111111111111111111111111");
+        assert_eq!(record["data_len"], 0);
+    }
+}
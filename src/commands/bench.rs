@@ -0,0 +1,426 @@
+use crate::commands::cluster;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use serde_json::{Value, json};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// RPC methods the benchmark can exercise.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Method {
+    #[value(name = "getSlot")]
+    Slot,
+    #[value(name = "getAccountInfo")]
+    AccountInfo,
+    #[value(name = "getBlock")]
+    Block,
+    #[value(name = "getSignaturesForAddress")]
+    SignaturesForAddress,
+}
+
+impl Method {
+    fn name(self) -> &'static str {
+        match self {
+            Method::Slot => "getSlot",
+            Method::AccountInfo => "getAccountInfo",
+            Method::Block => "getBlock",
+            Method::SignaturesForAddress => "getSignaturesForAddress",
+        }
+    }
+}
+
+/// Command-line options for `rpc-bench`.
+#[derive(clap::Args)]
+pub struct BenchArgs {
+    /// Endpoints to benchmark (cluster names or URLs; defaults to the configured RPC).
+    pub endpoints: Vec<String>,
+
+    /// Methods to call, comma separated (defaults to all).
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub methods: Vec<Method>,
+
+    /// Requests per method and endpoint.
+    #[arg(long, short = 'n', default_value_t = 20)]
+    pub iterations: usize,
+
+    /// Requests in flight at once.
+    #[arg(long, short = 'c', default_value_t = 4)]
+    pub concurrency: usize,
+
+    /// Account used by getAccountInfo and getSignaturesForAddress.
+    #[arg(long, default_value = "Vote111111111111111111111111111111111111111")]
+    pub address: String,
+
+    /// Per-request timeout in seconds.
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+}
+
+/// Outcome counts and latencies of one method against one endpoint.
+#[derive(Default)]
+struct MethodStats {
+    latencies: Vec<Duration>,
+    errors: usize,
+    throttled: usize,
+    /// Wall-clock time for all iterations.
+    elapsed: Duration,
+    first_error: Option<String>,
+}
+
+impl MethodStats {
+    fn requests(&self) -> usize {
+        self.latencies.len() + self.errors
+    }
+
+    /// Nearest-rank percentile of successful request latencies.
+    fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = ((p / 100.0) * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+}
+
+/// Rate limiting shows up as HTTP 429 or a provider specific message.
+fn is_throttled(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("429") || error.contains("too many requests") || error.contains("rate limit")
+}
+
+/// Parameters for `method`, resolved once per endpoint.
+fn params(client: &RpcClient, method: Method, address: &str) -> Result<Value> {
+    Ok(match method {
+        Method::Slot => Value::Null,
+        Method::AccountInfo => json!([address, { "encoding": "base64" }]),
+        Method::SignaturesForAddress => json!([address, { "limit": 10 }]),
+        Method::Block => {
+            // A recent block that exists, well behind the tip
+            let tip = client.get_slot_with_commitment(CommitmentConfig::finalized())?;
+            let slot = client
+                .get_blocks_with_limit(tip.saturating_sub(100), 1)?
+                .first()
+                .copied()
+                .unwrap_or(tip);
+            json!([slot, {
+                "encoding": "json",
+                "maxSupportedTransactionVersion": 0,
+                "transactionDetails": "signatures",
+                "rewards": false,
+            }])
+        }
+    })
+}
+
+/// Send one raw JSON-RPC request, returning an error description on failure.
+///
+/// Requests bypass `RpcClient` so HTTP 429 responses are counted instead of
+/// being retried transparently.
+fn call(
+    http: &reqwest::blocking::Client,
+    url: &str,
+    body: &Value,
+) -> std::result::Result<(), String> {
+    let response = http
+        .post(url)
+        .json(body)
+        .send()
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status));
+    }
+    let reply: Value = response.json().map_err(|e| e.to_string())?;
+    match reply.get("error") {
+        Some(error) => Err(error["message"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Call `method` `iterations` times from `concurrency` worker threads.
+fn run_method(url: &str, method: Method, params: &Value, args: &BenchArgs) -> MethodStats {
+    let mut body = json!({ "jsonrpc": "2.0", "id": 1, "method": method.name() });
+    if !params.is_null() {
+        body["params"] = params.clone();
+    }
+    let next = AtomicUsize::new(0);
+    let stats = Mutex::new(MethodStats::default());
+    let started = Instant::now();
+    std::thread::scope(|scope| {
+        for _ in 0..args.concurrency.clamp(1, args.iterations.max(1)) {
+            scope.spawn(|| {
+                let http = reqwest::blocking::Client::builder()
+                    .timeout(Duration::from_secs(args.timeout))
+                    .build()
+                    .expect("failed to build HTTP client");
+                while next.fetch_add(1, Ordering::Relaxed) < args.iterations {
+                    let sent = Instant::now();
+                    let result = call(&http, url, &body);
+                    let latency = sent.elapsed();
+                    let mut stats = stats.lock().unwrap();
+                    match result {
+                        Ok(()) => stats.latencies.push(latency),
+                        Err(e) => {
+                            stats.errors += 1;
+                            if is_throttled(&e) {
+                                stats.throttled += 1;
+                            }
+                            stats.first_error.get_or_insert(e);
+                        }
+                    }
+                }
+            });
+        }
+    });
+    let mut stats = stats.into_inner().unwrap();
+    stats.elapsed = started.elapsed();
+    stats.latencies.sort();
+    stats
+}
+
+fn ms(d: Option<Duration>) -> String {
+    d.map(|d| format!("{:.1} ms", d.as_secs_f64() * 1000.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Benchmark a mix of RPC methods against one or more endpoints.
+///
+/// Reports p50/p90/p99 latency of successful calls, error rate and the
+/// number of throttled (HTTP 429 / rate limited) calls per method.
+pub fn run(default_url: &str, args: &BenchArgs) -> Result<()> {
+    let urls: Vec<String> = if args.endpoints.is_empty() {
+        vec![default_url.to_string()]
+    } else {
+        args.endpoints
+            .iter()
+            .map(|e| cluster::resolve_url(e))
+            .collect::<Result<_>>()?
+    };
+    let methods = if args.methods.is_empty() {
+        vec![
+            Method::Slot,
+            Method::AccountInfo,
+            Method::Block,
+            Method::SignaturesForAddress,
+        ]
+    } else {
+        args.methods.clone()
+    };
+
+    // Per-endpoint totals across all methods, for the comparison table
+    let mut summary: Vec<(String, MethodStats)> = Vec::new();
+    for url in &urls {
+        println!(
+            "\n{}",
+            format!(
+                "--- RPC Bench: {} ({} x {} concurrent) ---",
+                url, args.iterations, args.concurrency
+            )
+            .bold()
+            .cyan()
+        );
+        let client = RpcClient::new_with_timeout(url.clone(), Duration::from_secs(args.timeout));
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("Method").style_spec("Fy"),
+            Cell::new("Requests").style_spec("Fc"),
+            Cell::new("Errors").style_spec("Fr"),
+            Cell::new("Throttled").style_spec("Fr"),
+            Cell::new("p50").style_spec("Fg"),
+            Cell::new("p90").style_spec("Fg"),
+            Cell::new("p99").style_spec("Fg"),
+            Cell::new("Req/s").style_spec("Fm"),
+        ]));
+        let mut total = MethodStats::default();
+        let mut failures = Vec::new();
+        for &method in &methods {
+            let stats = match params(&client, method, &args.address) {
+                Ok(params) => run_method(url, method, &params, args),
+                Err(e) => {
+                    failures.push(format!(
+                        "{}: could not prepare request: {}",
+                        method.name(),
+                        e
+                    ));
+                    continue;
+                }
+            };
+            let requests = stats.requests();
+            let error_rate = stats.errors as f64 / requests.max(1) as f64 * 100.0;
+            let errors = Cell::new(&format!("{} ({:.1}%)", stats.errors, error_rate));
+            table.add_row(Row::new(vec![
+                Cell::new(method.name()),
+                Cell::new(&requests.to_string()),
+                if stats.errors > 0 {
+                    errors.style_spec("Fr")
+                } else {
+                    errors
+                },
+                Cell::new(&stats.throttled.to_string()),
+                Cell::new(&ms(stats.percentile(50.0))),
+                Cell::new(&ms(stats.percentile(90.0))),
+                Cell::new(&ms(stats.percentile(99.0))),
+                Cell::new(&format!(
+                    "{:.1}",
+                    requests as f64 / stats.elapsed.as_secs_f64().max(f64::EPSILON)
+                )),
+            ]));
+            if let Some(e) = &stats.first_error {
+                failures.push(format!("{}: {}", method.name(), e));
+            }
+            total.latencies.extend(&stats.latencies);
+            total.errors += stats.errors;
+            total.throttled += stats.throttled;
+        }
+        table.printstd();
+        for failure in &failures {
+            println!("{} {}", "First error".red(), failure);
+        }
+        total.latencies.sort();
+        summary.push((url.clone(), total));
+    }
+
+    if summary.len() > 1 {
+        println!("\n{}", "--- Endpoint Comparison ---".bold().cyan());
+        summary.sort_by_key(|(_, s)| s.percentile(50.0).unwrap_or(Duration::MAX));
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("Endpoint").style_spec("Fy"),
+            Cell::new("p50").style_spec("Fg"),
+            Cell::new("p99").style_spec("Fg"),
+            Cell::new("Error Rate").style_spec("Fr"),
+            Cell::new("Throttled").style_spec("Fr"),
+        ]));
+        for (url, stats) in &summary {
+            table.add_row(Row::new(vec![
+                Cell::new(url),
+                Cell::new(&ms(stats.percentile(50.0))),
+                Cell::new(&ms(stats.percentile(99.0))),
+                Cell::new(&format!(
+                    "{:.1}%",
+                    stats.errors as f64 / stats.requests().max(1) as f64 * 100.0
+                )),
+                Cell::new(&stats.throttled.to_string()),
+            ]));
+        }
+        table.printstd();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Arc;
+
+    const DELAY: Duration = Duration::from_millis(20);
+
+    /// Answer JSON-RPC requests after `DELAY`: every fifth request is
+    /// throttled with HTTP 429 and the one after it gets an RPC error.
+    fn serve(stream: TcpStream, served: &AtomicUsize) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut stream = stream;
+        loop {
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    return;
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            std::thread::sleep(DELAY);
+            let reply = match served.fetch_add(1, Ordering::SeqCst) % 5 {
+                0 => {
+                    let head = "HTTP/1.1 429 Too Many Requests\r\nContent-Length: 0\r\n\r\n";
+                    stream.write_all(head.as_bytes()).unwrap();
+                    continue;
+                }
+                1 => {
+                    r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is behind"}}"#
+                }
+                _ => r#"{"jsonrpc":"2.0","id":1,"result":42}"#,
+            };
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                reply.len()
+            );
+            stream.write_all(head.as_bytes()).unwrap();
+            stream.write_all(reply.as_bytes()).unwrap();
+        }
+    }
+
+    /// Start the mock RPC server, returning its URL and request counter.
+    fn mock_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let served = Arc::new(AtomicUsize::new(0));
+        let counter = served.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let counter = counter.clone();
+                std::thread::spawn(move || serve(stream, &counter));
+            }
+        });
+        (url, served)
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let stats = MethodStats {
+            latencies: (1..=100).map(Duration::from_millis).collect(),
+            ..Default::default()
+        };
+        assert_eq!(stats.percentile(50.0), Some(Duration::from_millis(50)));
+        assert_eq!(stats.percentile(90.0), Some(Duration::from_millis(90)));
+        assert_eq!(stats.percentile(99.0), Some(Duration::from_millis(99)));
+        assert_eq!(MethodStats::default().percentile(50.0), None);
+    }
+
+    #[test]
+    fn run_method_against_mock_server() {
+        let (url, served) = mock_server();
+        let args = BenchArgs {
+            endpoints: Vec::new(),
+            methods: Vec::new(),
+            iterations: 20,
+            concurrency: 4,
+            address: String::new(),
+            timeout: 5,
+        };
+        let stats = run_method(&url, Method::Slot, &Value::Null, &args);
+
+        assert_eq!(served.load(Ordering::SeqCst), 20);
+        assert_eq!(stats.requests(), 20);
+        assert_eq!(stats.errors, 8);
+        assert_eq!(stats.throttled, 4);
+        assert_eq!(stats.latencies.len(), 12);
+        let (p50, p90, p99) = (
+            stats.percentile(50.0).unwrap(),
+            stats.percentile(90.0).unwrap(),
+            stats.percentile(99.0).unwrap(),
+        );
+        assert!(p50 >= DELAY);
+        assert!(p50 <= p90 && p90 <= p99);
+        assert_eq!(p99, *stats.latencies.last().unwrap());
+        assert!(stats.first_error.is_some());
+    }
+}
@@ -1,10 +1,30 @@
+use crate::labels;
+use crate::rpc;
 use anyhow::Result;
 use colored::*;
 use prettytable::{Cell, Row, Table};
+use solana_client::pubsub_client::PubsubClient;
 use solana_client::rpc_client::RpcClient;
 use solana_client::rpc_config::RpcBlockConfig;
+use solana_client::rpc_request::MAX_GET_SLOT_LEADERS;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
+use solana_sdk::compute_budget;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::vote;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    EncodedTransactionWithStatusMeta, TransactionDetails, UiConfirmedBlock, UiTransactionEncoding,
+};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Compute unit limit per instruction when a transaction does not set one.
+const DEFAULT_UNITS_PER_INSTRUCTION: u64 = 200_000;
+/// Largest compute unit limit a transaction can request.
+const MAX_COMPUTE_UNITS: u64 = 1_400_000;
+/// Delay between polls for new blocks when not using the websocket.
+const POLL_INTERVAL: Duration = Duration::from_millis(400);
 
 /// Fetch and display detailed block information.
 pub fn get_block(client: &RpcClient, slot: u64) -> Result<()> {
@@ -66,3 +86,392 @@ pub fn get_block(client: &RpcClient, slot: u64) -> Result<()> {
 
     Ok(())
 }
+
+/// Totals of one block, optionally restricted to transactions using a program.
+#[derive(Default)]
+struct BlockSummary {
+    transactions: usize,
+    votes: usize,
+    compute_units: u64,
+    fees: u64,
+    priority_fees: u64,
+}
+
+/// A transaction matching the `--program` filter of `block-follow`.
+struct MatchedTx {
+    signature: String,
+    compute_units: u64,
+    fee: u64,
+    failed: bool,
+}
+
+/// Whether a transaction references `program`, statically or via a lookup table.
+fn mentions(tx: &EncodedTransactionWithStatusMeta, keys: &[Pubkey], program: &Pubkey) -> bool {
+    if keys.contains(program) {
+        return true;
+    }
+    let program = program.to_string();
+    match tx.meta.as_ref().map(|m| &m.loaded_addresses) {
+        Some(OptionSerializer::Some(loaded)) => loaded
+            .writable
+            .iter()
+            .chain(&loaded.readonly)
+            .any(|k| *k == program),
+        _ => false,
+    }
+}
+
+/// Priority fee requested by a message's compute budget instructions:
+/// the compute unit price (in micro-lamports) times the unit limit.
+fn priority_fee(message: &VersionedMessage) -> u64 {
+    let keys = message.static_account_keys();
+    let mut price = 0u64;
+    let mut limit = None;
+    let mut instructions = 0u64;
+    for ix in message.instructions() {
+        if keys.get(ix.program_id_index as usize) != Some(&compute_budget::id()) {
+            instructions += 1;
+            continue;
+        }
+        // SetComputeUnitLimit(u32) and SetComputeUnitPrice(u64)
+        match ix.data.split_first() {
+            Some((2, rest)) if rest.len() >= 4 => {
+                limit = Some(u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64)
+            }
+            Some((3, rest)) if rest.len() >= 8 => {
+                price = u64::from_le_bytes(rest[..8].try_into().unwrap())
+            }
+            _ => {}
+        }
+    }
+    let limit = limit
+        .unwrap_or(instructions * DEFAULT_UNITS_PER_INSTRUCTION)
+        .min(MAX_COMPUTE_UNITS);
+    (price as u128 * limit as u128).div_ceil(1_000_000) as u64
+}
+
+/// Summarize a block with full (base64) transactions.
+fn summarize(block: &UiConfirmedBlock, program: Option<&Pubkey>) -> (BlockSummary, Vec<MatchedTx>) {
+    let mut summary = BlockSummary::default();
+    let mut matched = Vec::new();
+    for tx in block.transactions.iter().flatten() {
+        let Some(decoded) = tx.transaction.decode() else {
+            continue;
+        };
+        let keys = decoded.message.static_account_keys();
+        let compute_units = match tx.meta.as_ref().map(|m| &m.compute_units_consumed) {
+            Some(OptionSerializer::Some(units)) => *units,
+            _ => 0,
+        };
+        let fee = tx.meta.as_ref().map_or(0, |m| m.fee);
+        let priority_fee = priority_fee(&decoded.message);
+
+        summary.transactions += 1;
+        if keys.contains(&vote::program::id()) {
+            summary.votes += 1;
+        }
+        summary.compute_units += compute_units;
+        summary.fees += fee;
+        summary.priority_fees += priority_fee;
+
+        if let Some(program) = program
+            && mentions(tx, keys, program)
+        {
+            matched.push(MatchedTx {
+                signature: decoded.signatures[0].to_string(),
+                compute_units,
+                fee,
+                failed: tx.meta.as_ref().is_some_and(|m| m.err.is_some()),
+            });
+        }
+    }
+    (summary, matched)
+}
+
+/// Leaders of `count` slots from `start`, in requests the RPC accepts.
+fn slot_leaders(client: &RpcClient, start: u64, count: u64) -> Result<Vec<Pubkey>> {
+    let mut leaders = Vec::with_capacity(count as usize);
+    let end = start + count;
+    let mut slot = start;
+    while slot < end {
+        let limit = (end - slot).min(MAX_GET_SLOT_LEADERS as u64);
+        leaders.extend(client.get_slot_leaders(slot, limit)?);
+        slot += limit;
+    }
+    Ok(leaders)
+}
+
+/// Print each new confirmed block as it lands.
+///
+/// New slots are announced by a `slotSubscribe` websocket (or, with `poll`
+/// or when the websocket is unavailable, by polling); the confirmed blocks
+/// since the last one are then listed with `getBlocks` and fetched. Each
+/// block shows its leader, transaction counts, compute units and fees.
+/// With `program`, only transactions referencing it are listed under each
+/// block. Stops after `count` blocks when given.
+pub fn follow(
+    rpc_url: &str,
+    program: Option<&str>,
+    poll: bool,
+    count: Option<usize>,
+) -> Result<()> {
+    let program = program
+        .map(|p| Pubkey::from_str(p).map_err(|_| anyhow::anyhow!("Invalid program address")))
+        .transpose()?;
+    let commitment = CommitmentConfig::confirmed();
+    let client = RpcClient::new_with_commitment(rpc_url.to_string(), commitment);
+    let config = RpcBlockConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        transaction_details: Some(TransactionDetails::Full),
+        rewards: Some(false),
+        commitment: Some(commitment),
+        max_supported_transaction_version: Some(0),
+    };
+
+    println!("\n{}", "--- Following Blocks ---".bold().cyan());
+    let ws_url = rpc::ws_url(rpc_url);
+    let mut subscription = if poll {
+        None
+    } else {
+        match PubsubClient::slot_subscribe(&ws_url) {
+            Ok(subscription) => Some(subscription),
+            Err(e) => {
+                println!(
+                    "{:<25} : {} ({})",
+                    "WebSocket",
+                    "Unavailable, polling instead".yellow(),
+                    e
+                );
+                None
+            }
+        }
+    };
+    println!(
+        "{:<25} : {}",
+        "New Slots From",
+        if subscription.is_some() {
+            ws_url.as_str()
+        } else {
+            "getBlocks polling"
+        }
+    );
+    if let Some(program) = &program {
+        println!(
+            "{:<25} : {}",
+            "Program Filter",
+            labels::display(&program.to_string()).magenta()
+        );
+    }
+    println!(
+        "\n{:<12} {:>6} {:>6} {:>8} {:>12} {:>12} {:>12}  Leader",
+        "Slot", "Txs", "Votes", "Non-Vote", "CU", "Fees (SOL)", "Priority"
+    );
+
+    let mut last = client.get_slot()?;
+    let mut shown = 0usize;
+    while count.is_none_or(|n| shown < n) {
+        // A slot notification (or the poll delay) signals there may be new blocks
+        match &subscription {
+            Some((_, receiver)) => match receiver.recv_timeout(POLL_INTERVAL * 5) {
+                // Slots arrive faster than blocks are fetched; one wake-up
+                // covers every slot notified since
+                Ok(_) => while receiver.try_recv().is_ok() {},
+                Err(e) if e.is_disconnected() => {
+                    println!("{}", "WebSocket closed, polling instead.".yellow());
+                    if let Some((subscription, _)) = subscription.take() {
+                        rpc::close_subscription(subscription);
+                    }
+                }
+                Err(_) => {}
+            },
+            None => std::thread::sleep(POLL_INTERVAL),
+        }
+
+        let tip = client.get_slot()?;
+        if tip <= last {
+            continue;
+        }
+        let blocks = client.get_blocks_with_commitment(last + 1, Some(tip), commitment)?;
+        let leaders = slot_leaders(&client, last + 1, tip - last)?;
+        for slot in last + 1..=tip {
+            if count.is_some_and(|n| shown >= n) {
+                break;
+            }
+            let leader = leaders
+                .get((slot - last - 1) as usize)
+                .map(|l| labels::display(&l.to_string()))
+                .unwrap_or_default();
+            if !blocks.contains(&slot) {
+                println!(
+                    "{}",
+                    format!("{:<12} {:<61}  {}", slot, "skipped", leader).dimmed()
+                );
+                continue;
+            }
+            // One unavailable block should not end the stream
+            let block = match client.get_block_with_config(slot, config) {
+                Ok(block) => block,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        format!("{:<12} {:<61}  {}", slot, "unavailable", leader).red()
+                    );
+                    println!("  {} {}", "↳".red(), e);
+                    continue;
+                }
+            };
+            let (summary, matched) = summarize(&block, program.as_ref());
+            println!(
+                "{:<12} {:>6} {:>6} {:>8} {:>12} {:>12.6} {:>12.6}  {}",
+                slot.to_string().green(),
+                summary.transactions,
+                summary.votes,
+                summary.transactions - summary.votes,
+                summary.compute_units,
+                summary.fees as f64 / 1_000_000_000.0,
+                summary.priority_fees as f64 / 1_000_000_000.0,
+                leader
+            );
+            for tx in &matched {
+                let status = if tx.failed {
+                    "FAILED".red()
+                } else {
+                    "ok".green()
+                };
+                println!(
+                    "  {} {}  {} CU  {} lamports  {}",
+                    "↳".magenta(),
+                    tx.signature.yellow(),
+                    tx.compute_units,
+                    tx.fee,
+                    status
+                );
+            }
+            shown += 1;
+        }
+        last = tip;
+    }
+    if let Some((subscription, _)) = subscription {
+        rpc::close_subscription(subscription);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use serde_json::json;
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+    use solana_sdk::instruction::Instruction;
+    use solana_sdk::transaction::Transaction;
+
+    fn encoded(
+        instructions: &[Instruction],
+        fee: u64,
+        compute_units: u64,
+        loaded: &[Pubkey],
+    ) -> EncodedTransactionWithStatusMeta {
+        let payer = Pubkey::new_unique();
+        let tx = Transaction::new_with_payer(instructions, Some(&payer));
+        let data =
+            base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&tx).unwrap());
+        let loaded: Vec<String> = loaded.iter().map(|k| k.to_string()).collect();
+        serde_json::from_value(json!({
+            "transaction": [data, "base64"],
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": fee,
+                "preBalances": [],
+                "postBalances": [],
+                "loadedAddresses": { "writable": [], "readonly": loaded },
+                "computeUnitsConsumed": compute_units
+            }
+        }))
+        .unwrap()
+    }
+
+    fn block(transactions: Vec<EncodedTransactionWithStatusMeta>) -> UiConfirmedBlock {
+        UiConfirmedBlock {
+            previous_blockhash: String::new(),
+            blockhash: String::new(),
+            parent_slot: 0,
+            transactions: Some(transactions),
+            signatures: None,
+            rewards: None,
+            block_time: None,
+            block_height: None,
+        }
+    }
+
+    fn call(program: Pubkey) -> Instruction {
+        Instruction::new_with_bytes(program, &[], Vec::new())
+    }
+
+    #[test]
+    fn mentions_static_and_looked_up_programs() {
+        let program = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let tx = encoded(&[call(program)], 5000, 0, &[]);
+        let decoded = tx.transaction.decode().unwrap();
+        let keys = decoded.message.static_account_keys();
+        assert!(mentions(&tx, keys, &program));
+        assert!(!mentions(&tx, keys, &other));
+
+        let tx = encoded(&[call(other)], 5000, 0, &[program]);
+        let decoded = tx.transaction.decode().unwrap();
+        assert!(mentions(
+            &tx,
+            decoded.message.static_account_keys(),
+            &program
+        ));
+    }
+
+    #[test]
+    fn summarize_counts_priority_fees_from_compute_budget() {
+        let program = Pubkey::new_unique();
+        let block = block(vec![
+            // 1_000_000 micro-lamports x 300_000 units
+            encoded(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+                    ComputeBudgetInstruction::set_compute_unit_price(1_000_000),
+                    call(program),
+                ],
+                305_000,
+                1_000,
+                &[],
+            ),
+            // Default limit of two instructions: 10 x 400_000 units
+            encoded(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_price(10),
+                    call(Pubkey::new_unique()),
+                    call(Pubkey::new_unique()),
+                ],
+                5_004,
+                2_000,
+                &[],
+            ),
+            // Precompile signatures raise the fee without any priority
+            encoded(&[call(solana_sdk::ed25519_program::id())], 15_000, 0, &[]),
+            encoded(&[call(vote::program::id())], 5_000, 2_100, &[]),
+        ]);
+
+        let (summary, matched) = summarize(&block, Some(&program));
+        assert_eq!(summary.transactions, 4);
+        assert_eq!(summary.votes, 1);
+        assert_eq!(summary.compute_units, 5_100);
+        assert_eq!(summary.fees, 330_004);
+        assert_eq!(summary.priority_fees, 300_004);
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].fee, 305_000);
+        assert_eq!(matched[0].compute_units, 1_000);
+        assert!(!matched[0].failed);
+
+        let (_, matched) = summarize(&block, None);
+        assert!(matched.is_empty());
+    }
+}
@@ -1,14 +1,48 @@
+use crate::commands::watch::Metrics;
 use crate::config::Config;
+use crate::labels;
+use crate::utils;
 use anyhow::Result;
 use colored::*;
+use prettytable::{Cell, Row, Table};
+use serde_json::Value;
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcRequest;
+use solana_sdk::clock::Slot;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
 
-pub fn set(cfg: &mut Config, name: String) -> Result<()> {
-    let url = match name.as_str() {
+/// Public RPC endpoint for a cluster name (defaults to Devnet).
+pub fn url_for(name: &str) -> &'static str {
+    match name {
         "mainnet" | "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
         "testnet" | "t" => "https://api.testnet.solana.com",
         _ => "https://api.devnet.solana.com",
-    };
+    }
+}
+
+/// RPC URL for a cluster name, or the input itself when it is already a URL.
+///
+/// Unlike [`url_for`], unknown names are an error rather than Devnet.
+pub fn resolve_url(input: &str) -> Result<String> {
+    if input.contains("://") {
+        return Ok(input.to_string());
+    }
+    match input {
+        "mainnet" | "mainnet-beta" | "m" | "testnet" | "t" | "devnet" | "d" => {
+            Ok(url_for(input).to_string())
+        }
+        "localhost" | "l" => Ok("http://127.0.0.1:8899".to_string()),
+        _ => anyhow::bail!(
+            "Unknown cluster '{}' (expected mainnet, testnet, devnet, localhost or a URL)",
+            input
+        ),
+    }
+}
+
+pub fn set(cfg: &mut Config, name: String) -> Result<()> {
+    let url = url_for(&name);
     cfg.cluster = name;
     cfg.rpc_url = url.to_string();
     cfg.save()?;
@@ -37,12 +71,229 @@ pub fn info(client: &RpcClient) -> Result<()> {
     Ok(())
 }
 
-pub fn health(client: &RpcClient) -> Result<()> {
-    match client.get_health() {
-        Ok(_) => println!("{}", "Cluster Status: HEALTHY".green().bold()),
-        Err(e) => println!("{} ({})", "Cluster Status: UNHEALTHY".red().bold(), e),
+/// Print the node's `getHealth` status; returns it as 1 (healthy) or 0 for `--watch`.
+pub fn health(client: &RpcClient) -> Result<Metrics> {
+    let healthy = match client.get_health() {
+        Ok(_) => {
+            println!("{}", "Cluster Status: HEALTHY".green().bold());
+            true
+        }
+        Err(e) => {
+            println!("{} ({})", "Cluster Status: UNHEALTHY".red().bold(), e);
+            false
+        }
+    };
+    Ok(vec![("Healthy", healthy as u8 as f64)])
+}
+
+/// Genesis hashes of the public clusters, with their public RPC endpoints.
+const PUBLIC_CLUSTERS: [(&str, &str); 3] = [
+    (
+        "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d",
+        "https://api.mainnet-beta.solana.com",
+    ),
+    (
+        "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY",
+        "https://api.testnet.solana.com",
+    ),
+    (
+        "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG",
+        "https://api.devnet.solana.com",
+    ),
+];
+
+/// Public endpoint of the cluster the configured endpoint belongs to.
+///
+/// The cluster is identified by its genesis hash, so a private mainnet
+/// node is compared against public mainnet; localnets and other clusters
+/// have no public reference and need `--against`.
+pub fn reference_url(client: &RpcClient) -> Result<String> {
+    let hash = client.get_genesis_hash()?.to_string();
+    match public_endpoint(&hash) {
+        Some(url) => Ok(url.to_string()),
+        None => anyhow::bail!(
+            "Genesis hash {} is not a public cluster; pass --against to choose endpoints to compare with",
+            hash
+        ),
+    }
+}
+
+fn public_endpoint(genesis_hash: &str) -> Option<&'static str> {
+    PUBLIC_CLUSTERS
+        .iter()
+        .find(|(hash, _)| *hash == genesis_hash)
+        .map(|(_, url)| *url)
+}
+
+/// Exit code when the configured endpoint lags behind the others.
+pub const EXIT_LAGGING: i32 = 2;
+/// Exit code when the configured endpoint is unhealthy or unreachable.
+pub const EXIT_UNHEALTHY: i32 = 3;
+
+/// One endpoint's answers to the deep health probes.
+struct Probe {
+    url: String,
+    /// `getHealth` result; `Err` carries the node's reason.
+    health: std::result::Result<(), String>,
+    slot: Option<Slot>,
+    block_height: Option<u64>,
+    min_ledger_slot: Option<Slot>,
+    first_block: Option<Slot>,
+    latency: Duration,
+}
+
+fn probe(url: &str) -> Probe {
+    let client = RpcClient::new_with_timeout_and_commitment(
+        url.to_string(),
+        Duration::from_secs(10),
+        CommitmentConfig::processed(),
+    );
+    let started = Instant::now();
+    let slot = client.get_slot().ok();
+    let latency = started.elapsed();
+    Probe {
+        url: url.to_string(),
+        health: client.get_health().map_err(|e| e.to_string()),
+        slot,
+        block_height: client.get_block_height().ok(),
+        min_ledger_slot: client.minimum_ledger_slot().ok(),
+        first_block: client.get_first_available_block().ok(),
+        latency,
+    }
+}
+
+/// Compare the configured endpoint against other endpoints.
+///
+/// All endpoints are probed concurrently at processed commitment so their
+/// slots are comparable. Returns the process exit code: 0 when healthy,
+/// [`EXIT_LAGGING`] when more than `max_lag` slots behind the best
+/// endpoint and [`EXIT_UNHEALTHY`] when `getHealth` fails or the endpoint
+/// does not answer, along with the configured endpoint's slot, lag and
+/// latency for `--watch`.
+pub fn deep_health(rpc_url: &str, others: &[String], max_lag: u64) -> Result<(i32, Metrics)> {
+    let urls = endpoints(rpc_url, others)?;
+    let probes: Vec<Probe> = std::thread::scope(|scope| {
+        let handles: Vec<_> = urls
+            .iter()
+            .map(|url| scope.spawn(move || probe(url)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("probe thread panicked"))
+            .collect()
+    });
+
+    let best_slot = probes.iter().filter_map(|p| p.slot).max();
+    let best_height = probes.iter().filter_map(|p| p.block_height).max();
+    let show = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_else(|| "-".into());
+
+    println!("\n{}", "--- Deep Health Check ---".bold().cyan());
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Endpoint").style_spec("Fy"),
+        Cell::new("Health").style_spec("Fg"),
+        Cell::new("Slot").style_spec("Fc"),
+        Cell::new("Behind").style_spec("Fr"),
+        Cell::new("Block Height").style_spec("Fc"),
+        Cell::new("Height Lag").style_spec("Fr"),
+        Cell::new("Min Ledger Slot").style_spec("Fm"),
+        Cell::new("First Block").style_spec("Fm"),
+        Cell::new("Latency").style_spec("Fc"),
+    ]));
+    for (i, p) in probes.iter().enumerate() {
+        let name = if i == 0 {
+            format!("{} (configured)", p.url)
+        } else {
+            p.url.clone()
+        };
+        let behind = p.slot.zip(best_slot).map(|(s, best)| best - s);
+        let behind_cell = Cell::new(&show(behind));
+        table.add_row(Row::new(vec![
+            Cell::new(&name),
+            match (&p.health, p.slot) {
+                (_, None) => Cell::new("down").style_spec("Fr"),
+                (Ok(()), _) => Cell::new("ok").style_spec("Fg"),
+                (Err(_), _) => Cell::new("unhealthy").style_spec("Fr"),
+            },
+            Cell::new(&show(p.slot)),
+            if behind.is_some_and(|b| b > max_lag) {
+                behind_cell.style_spec("Fr")
+            } else {
+                behind_cell
+            },
+            Cell::new(&show(p.block_height)),
+            Cell::new(&show(
+                p.block_height.zip(best_height).map(|(h, best)| best - h),
+            )),
+            Cell::new(&show(p.min_ledger_slot)),
+            Cell::new(&show(p.first_block)),
+            Cell::new(&if p.slot.is_some() {
+                format!("{} ms", p.latency.as_millis())
+            } else {
+                "unreachable".to_string()
+            }),
+        ]));
+    }
+    table.printstd();
+    for p in &probes {
+        if let Err(e) = &p.health {
+            println!("{} {}: {}", "Unhealthy:".red(), p.url, e);
+        }
+    }
+
+    let own = &probes[0];
+    let (code, verdict) = verdict(own.slot, own.health.is_ok(), best_slot, max_lag);
+    let verdict = match code {
+        0 => verdict.green().bold(),
+        EXIT_LAGGING => verdict.yellow().bold(),
+        _ => verdict.red().bold(),
+    };
+    println!("\n{:<25} : {}", "Configured Endpoint", verdict);
+    let mut metrics = Metrics::new();
+    if let Some(slot) = own.slot {
+        metrics.push(("Slot", slot as f64));
+        metrics.push((
+            "Slots Behind",
+            best_slot.map_or(0, |best| best - slot) as f64,
+        ));
+        metrics.push(("Latency ms", own.latency.as_millis() as f64));
+    }
+    Ok((code, metrics))
+}
+
+/// The configured endpoint followed by the distinct `others` it is compared with.
+fn endpoints(rpc_url: &str, others: &[String]) -> Result<Vec<String>> {
+    let mut urls = vec![rpc_url.to_string()];
+    for other in others {
+        let url = resolve_url(other)?;
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    if urls.len() == 1 {
+        anyhow::bail!(
+            "Nothing to compare against: every --against endpoint is the configured one ({})",
+            rpc_url
+        );
+    }
+    Ok(urls)
+}
+
+/// Exit code and verdict for the configured endpoint.
+fn verdict(
+    slot: Option<Slot>,
+    healthy: bool,
+    best_slot: Option<Slot>,
+    max_lag: u64,
+) -> (i32, &'static str) {
+    match slot {
+        None => (EXIT_UNHEALTHY, "UNREACHABLE"),
+        Some(_) if !healthy => (EXIT_UNHEALTHY, "UNHEALTHY"),
+        Some(slot) if best_slot.is_some_and(|best| best - slot > max_lag) => {
+            (EXIT_LAGGING, "LAGGING")
+        }
+        Some(_) => (0, "HEALTHY"),
     }
-    Ok(())
 }
 
 pub fn genesis(client: &RpcClient) -> Result<()> {
@@ -59,7 +310,7 @@ pub fn get_nodes(client: &RpcClient) -> Result<()> {
             .bold()
             .cyan()
     );
-    println!("{:<45} | {:<20} | {}", "Pubkey", "Version", "RPC");
+    println!("{:<45} | {:<20} | RPC", "Pubkey", "Version");
     println!("{}", "-".repeat(80));
     for node in nodes.iter().take(20) {
         let rpc = node
@@ -70,10 +321,226 @@ pub fn get_nodes(client: &RpcClient) -> Result<()> {
             .version
             .clone()
             .unwrap_or_else(|| "Unknown".to_string());
-        println!("{:<45} | {:<20} | {}", node.pubkey, ver, rpc);
+        println!(
+            "{:<45} | {:<20} | {}",
+            labels::display(&node.pubkey),
+            ver,
+            rpc
+        );
     }
     if nodes.len() > 20 {
         println!("... and {} more.", nodes.len() - 20);
     }
     Ok(())
 }
+
+/// Validator client family of a gossip node.
+///
+/// Uses the client id when the node reports one (matching the gossip
+/// `ClientId` numbering); otherwise falls back to the version string:
+/// Frankendancer/Firedancer report `0.x` versions, Agave `2.x` and later,
+/// and the original Solana Labs client `1.x`. Jito cannot be told apart
+/// from Agave by version alone.
+fn client_family(version: &str, client_id: Option<&Value>) -> &'static str {
+    let id = client_id.and_then(|id| match id {
+        Value::Number(n) => n.as_u64().map(|n| n.to_string()),
+        Value::String(s) => Some(s.to_lowercase()),
+        _ => None,
+    });
+    match id.as_deref() {
+        Some("0" | "solanalabs") => return "Solana Labs",
+        Some("1" | "jitolabs" | "jito") => return "Jito",
+        Some("2" | "firedancer") => return "Firedancer",
+        Some("3" | "agave") => return "Agave",
+        _ => {}
+    }
+    match semver::Version::parse(version) {
+        Ok(v) if v.major == 0 => "Firedancer",
+        Ok(v) if v.major == 1 => "Solana Labs",
+        Ok(_) => "Agave",
+        Err(_) => "Unknown",
+    }
+}
+
+/// Nodes and stake running one software version of one client.
+#[derive(Default)]
+struct VersionGroup {
+    feature_sets: BTreeMap<u64, usize>,
+    nodes: usize,
+    staked_nodes: usize,
+    stake: u64,
+}
+
+/// Group gossip nodes and their stake by software version and client.
+///
+/// With `min_version`, stake on older versions of the same client line is
+/// flagged. Firedancer versions use their own numbering and are never
+/// compared against an Agave minimum (and vice versa).
+pub fn versions(client: &RpcClient, min_version: Option<&str>) -> Result<()> {
+    let min = min_version
+        .map(|v| {
+            semver::Version::parse(v.trim_start_matches('v'))
+                .map_err(|_| anyhow::anyhow!("Invalid version '{}' (expected e.g. 2.0.15)", v))
+        })
+        .transpose()?;
+    // Requested raw so client metadata beyond the typed response is kept
+    let nodes: Vec<Value> = client.send(RpcRequest::GetClusterNodes, Value::Null)?;
+    let vote_accounts = client.get_vote_accounts()?;
+    let mut stake_by_node: HashMap<&str, u64> = HashMap::new();
+    for vote in vote_accounts
+        .current
+        .iter()
+        .chain(&vote_accounts.delinquent)
+    {
+        *stake_by_node.entry(vote.node_pubkey.as_str()).or_default() += vote.activated_stake;
+    }
+    let total_stake: u64 = stake_by_node.values().sum();
+
+    let mut groups: BTreeMap<(String, &str), VersionGroup> = BTreeMap::new();
+    for node in &nodes {
+        let version = node["version"].as_str().unwrap_or("unknown").to_string();
+        let stake = node["pubkey"]
+            .as_str()
+            .and_then(|pk| stake_by_node.get(pk))
+            .copied()
+            .unwrap_or(0);
+        let family = client_family(&version, node.get("clientId"));
+        let group = groups.entry((version, family)).or_default();
+        group.nodes += 1;
+        if stake > 0 {
+            group.staked_nodes += 1;
+        }
+        group.stake += stake;
+        if let Some(fs) = node["featureSet"].as_u64() {
+            *group.feature_sets.entry(fs).or_default() += 1;
+        }
+    }
+    let mut groups: Vec<((String, &str), VersionGroup)> = groups.into_iter().collect();
+    groups.sort_by(|a, b| b.1.stake.cmp(&a.1.stake).then(b.1.nodes.cmp(&a.1.nodes)));
+
+    let is_outdated = |version: &str| -> bool {
+        let (Some(min), Ok(v)) = (&min, semver::Version::parse(version)) else {
+            return false;
+        };
+        // Only compare within the same numbering scheme
+        (v.major == 0) == (min.major == 0) && v < *min
+    };
+
+    println!(
+        "\n{}",
+        format!("--- Cluster Versions ({} nodes) ---", nodes.len())
+            .bold()
+            .cyan()
+    );
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Version").style_spec("Fy"),
+        Cell::new("Client").style_spec("Fm"),
+        Cell::new("Feature Set").style_spec("Fc"),
+        Cell::new("Nodes").style_spec("Fc"),
+        Cell::new("Staked Nodes").style_spec("Fc"),
+        Cell::new("Stake (SOL)").style_spec("Fg"),
+        Cell::new("% Stake").style_spec("Fg"),
+    ]));
+    for ((version, family), group) in &groups {
+        let feature_sets = group
+            .feature_sets
+            .keys()
+            .map(|fs| fs.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let version_cell = Cell::new(version);
+        table.add_row(Row::new(vec![
+            if is_outdated(version) {
+                version_cell.style_spec("Fr")
+            } else {
+                version_cell
+            },
+            Cell::new(family),
+            Cell::new(&feature_sets),
+            Cell::new(&group.nodes.to_string()),
+            Cell::new(&group.staked_nodes.to_string()),
+            Cell::new(&utils::format_sol(group.stake as i128)),
+            Cell::new(&format!("{:.2}%", utils::percent(group.stake, total_stake))),
+        ]));
+    }
+    table.printstd();
+
+    let mut families: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
+    for ((_, family), group) in &groups {
+        let entry = families.entry(family).or_default();
+        entry.0 += group.nodes;
+        entry.1 += group.stake;
+    }
+    println!("\n{}", "--- Client Diversity ---".bold().cyan());
+    for (family, (count, stake)) in &families {
+        println!(
+            "{:<25} : {:>6.2}% of stake ({} nodes)",
+            family,
+            utils::percent(*stake, total_stake),
+            count
+        );
+    }
+
+    if let Some(min) = &min {
+        let (count, stake) = groups
+            .iter()
+            .filter(|((version, _), _)| is_outdated(version))
+            .fold((0, 0u64), |(n, s), (_, g)| (n + g.nodes, s + g.stake));
+        println!(
+            "\n{:<25} : {} nodes, {} of stake",
+            format!("Below {}", min),
+            count,
+            format!("{:.2}%", utils::percent(stake, total_stake))
+                .red()
+                .bold()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL: &str = "http://127.0.0.1:8899";
+
+    #[test]
+    fn endpoints_dedupe_against_configured() {
+        let urls = endpoints(LOCAL, &["localhost".into(), "mainnet".into(), "m".into()]).unwrap();
+        assert_eq!(urls, [LOCAL, "https://api.mainnet-beta.solana.com"]);
+    }
+
+    #[test]
+    fn endpoints_require_another_endpoint() {
+        assert!(endpoints(LOCAL, &["l".into(), LOCAL.into()]).is_err());
+        assert!(endpoints(LOCAL, &[]).is_err());
+        assert!(endpoints(LOCAL, &["localnet".into()]).is_err());
+    }
+
+    #[test]
+    fn reference_follows_genesis_hash() {
+        assert_eq!(
+            public_endpoint("5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d"),
+            Some("https://api.mainnet-beta.solana.com")
+        );
+        assert_eq!(
+            public_endpoint("EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG"),
+            Some("https://api.devnet.solana.com")
+        );
+        // A local test validator has its own genesis
+        assert_eq!(public_endpoint("11111111111111111111111111111111"), None);
+    }
+
+    #[test]
+    fn verdict_exit_codes() {
+        assert_eq!(verdict(None, true, Some(100), 10).0, EXIT_UNHEALTHY);
+        assert_eq!(verdict(Some(100), false, Some(100), 10).0, EXIT_UNHEALTHY);
+        assert_eq!(
+            verdict(Some(89), true, Some(100), 10),
+            (EXIT_LAGGING, "LAGGING")
+        );
+        assert_eq!(verdict(Some(90), true, Some(100), 10), (0, "HEALTHY"));
+        assert_eq!(verdict(Some(100), true, Some(100), 0), (0, "HEALTHY"));
+    }
+}
@@ -0,0 +1,661 @@
+use crate::commands::account::{self, HistoryFilter};
+use crate::commands::rent;
+use crate::commands::transaction::{self, ResolvedKey};
+use crate::utils;
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiTransactionStatusMeta, UiTransactionTokenBalance,
+    option_serializer::OptionSerializer,
+};
+use spl_token_2022::state::Account as TokenAccount;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::str::FromStr;
+
+const STAKE_PROGRAM: &str = "Stake11111111111111111111111111111111111111";
+const VOTE_PROGRAM: &str = "Vote111111111111111111111111111111111111111";
+
+/// One balance change of the exported address within a transaction.
+///
+/// `sol_delta` is the raw lamport balance change (fees included) expressed
+/// in SOL; `fee_paid` is only set on the first row of a transaction so the
+/// column can be summed safely.
+#[derive(Serialize)]
+struct ExportRow {
+    timestamp: String,
+    signature: String,
+    slot: u64,
+    sol_delta: String,
+    token_mint: String,
+    token_delta: String,
+    fee_paid: String,
+    counterparty: String,
+    action: String,
+}
+
+/// A token balance change keyed by token account index.
+struct TokenDelta {
+    mint: String,
+    delta: i128,
+    decimals: u8,
+    counterparty: Option<String>,
+}
+
+/// Export every SOL and token balance change of an address to CSV.
+///
+/// Walks the signature history (optionally bounded by `from`/`to` dates),
+/// fetches each transaction oldest first and appends its rows to `out`.
+/// Every processed signature gets at least one row, and signatures already
+/// present in an existing file are skipped, so an interrupted export can
+/// simply be re-run.
+pub fn export(
+    client: &RpcClient,
+    address_str: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+    out: &Path,
+) -> Result<()> {
+    let pubkey = Pubkey::from_str(address_str).map_err(|_| anyhow::anyhow!("Invalid Address"))?;
+    let filter = HistoryFilter {
+        since_time: from.map(|d| utils::parse_date(d, false)).transpose()?,
+        until_time: to.map(|d| utils::parse_date(d, true)).transpose()?,
+        ..Default::default()
+    };
+
+    let rent = rent::fetch_rent(client)?;
+    let done = read_exported_signatures(out)?;
+    if !done.is_empty() {
+        println!(
+            "Resuming export: {} transactions already in {}",
+            done.len(),
+            out.display()
+        );
+    }
+
+    println!("Fetching signature history for {}...", address_str);
+    let mut history = account::fetch_history(client, &pubkey, &filter)?;
+    // Export oldest first so an interrupted run leaves a contiguous prefix.
+    history.reverse();
+    let pending: Vec<_> = history
+        .iter()
+        .filter(|s| !done.contains(&s.signature))
+        .collect();
+
+    let file = OpenOptions::new().create(true).append(true).open(out)?;
+    let write_header = file.metadata()?.len() == 0;
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(write_header)
+        .from_writer(file);
+
+    let mut rows_written = 0usize;
+    for (i, sig_info) in pending.iter().enumerate() {
+        eprint!(
+            "\r[{}/{}] {}",
+            i + 1,
+            pending.len(),
+            &sig_info.signature[..16]
+        );
+        let signature = Signature::from_str(&sig_info.signature)?;
+        let tx = transaction::fetch(client, &signature)?;
+        for row in balance_rows(&tx, address_str, &sig_info.signature, &rent) {
+            writer.serialize(row)?;
+            rows_written += 1;
+        }
+        // Flush per transaction so a resumed run never sees half a transaction.
+        writer.flush()?;
+    }
+    if !pending.is_empty() {
+        eprintln!();
+    }
+
+    println!("\n{}", "--- Account Export ---".bold().cyan());
+    println!("{:<25} : {}", "Address", address_str.yellow());
+    println!("{:<25} : {}", "Transactions Found", history.len());
+    println!(
+        "{:<25} : {}",
+        "Skipped (Already Done)",
+        history.len() - pending.len()
+    );
+    println!(
+        "{:<25} : {}",
+        "Rows Written",
+        rows_written.to_string().green()
+    );
+    println!("{:<25} : {}", "Output File", out.display());
+    Ok(())
+}
+
+/// Signatures already written to a previous export file.
+fn read_exported_signatures(path: &Path) -> Result<HashSet<String>> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+    let mut reader = csv::Reader::from_path(path)?;
+    let column = reader
+        .headers()?
+        .iter()
+        .position(|h| h == "signature")
+        .ok_or_else(|| anyhow::anyhow!("{} is not an export file", path.display()))?;
+    let mut done = HashSet::new();
+    for record in reader.records() {
+        if let Some(sig) = record?.get(column) {
+            done.insert(sig.to_string());
+        }
+    }
+    Ok(done)
+}
+
+/// Build the export rows of one transaction for `address`.
+///
+/// Always returns at least one row, so the signature is recorded as done.
+/// `rent` is the cluster's rent, to tell token account rent from trades.
+fn balance_rows(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    address: &str,
+    signature: &str,
+    rent: &Rent,
+) -> Vec<ExportRow> {
+    let timestamp = tx
+        .block_time
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default();
+    let Some(meta) = &tx.transaction.meta else {
+        return vec![ExportRow {
+            timestamp,
+            signature: signature.to_string(),
+            slot: tx.slot,
+            sol_delta: String::new(),
+            token_mint: String::new(),
+            token_delta: String::new(),
+            fee_paid: String::new(),
+            counterparty: String::new(),
+            action: "unknown".to_string(),
+        }];
+    };
+    let keys = transaction::account_keys(tx);
+
+    let index = keys.iter().position(|k| k.pubkey == address);
+    let sol_delta = index
+        .and_then(|i| {
+            Some(*meta.post_balances.get(i)? as i128 - *meta.pre_balances.get(i)? as i128)
+        })
+        .unwrap_or(0);
+    let fee = if index == Some(0) { meta.fee } else { 0 };
+    let sol_counterparty = index.and_then(|i| sol_counterparty(&keys, meta, i, sol_delta));
+
+    let token_deltas = token_deltas(meta, &keys, address);
+    // Rent paid into new token accounts (or refunded by closing them) is
+    // not part of a token trade: creating an ATA to receive tokens is no swap
+    let mut sol_net = sol_delta + fee as i128;
+    let account_rent = index.map_or(0, |i| token_account_rent(meta, i, rent));
+    if !token_deltas.is_empty()
+        && account_rent.signum() == sol_net.signum()
+        && account_rent.abs() <= sol_net.abs()
+    {
+        sol_net -= account_rent;
+    }
+    let action = classify(&keys, meta.err.is_some(), sol_net, &token_deltas);
+
+    let mut rows = Vec::new();
+    let mut fee_cell = (fee > 0).then(|| utils::format_sol(fee as i128));
+    let mut push = |sol: String, mint: String, token: String, counterparty: Option<String>| {
+        rows.push(ExportRow {
+            timestamp: timestamp.clone(),
+            signature: signature.to_string(),
+            slot: tx.slot,
+            sol_delta: sol,
+            token_mint: mint,
+            token_delta: token,
+            fee_paid: fee_cell.take().unwrap_or_default(),
+            counterparty: counterparty.unwrap_or_default(),
+            action: action.to_string(),
+        });
+    };
+
+    if sol_delta != 0 || token_deltas.is_empty() {
+        push(
+            utils::format_sol(sol_delta),
+            String::new(),
+            String::new(),
+            sol_counterparty,
+        );
+    }
+    for delta in token_deltas {
+        push(
+            String::new(),
+            delta.mint,
+            utils::format_amount(delta.delta, delta.decimals),
+            delta.counterparty,
+        );
+    }
+    rows
+}
+
+/// Whether `lamports` is exactly the rent-exempt minimum of a token
+/// account of some size; Token-2022 extensions make them larger than 165 bytes.
+fn is_token_account_rent(lamports: u64, rent: &Rent) -> bool {
+    let base = rent.minimum_balance(0);
+    let per_byte = rent.minimum_balance(1).saturating_sub(base);
+    per_byte > 0
+        && lamports >= rent.minimum_balance(TokenAccount::LEN)
+        && (lamports - base).is_multiple_of(per_byte)
+}
+
+/// Lamports refunded by closed token accounts minus those paid into new ones.
+///
+/// A token account is created when it only has a post token balance and
+/// closed when it only has a pre token balance; its lamports count when
+/// they are exactly the rent-exempt minimum for its size. `own` (the
+/// exported address) is ignored.
+fn token_account_rent(meta: &UiTransactionStatusMeta, own: usize, rent: &Rent) -> i128 {
+    let indices = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>| -> HashSet<usize> {
+        match balances {
+            OptionSerializer::Some(b) => b.iter().map(|b| b.account_index as usize).collect(),
+            _ => HashSet::new(),
+        }
+    };
+    let pre = indices(&meta.pre_token_balances);
+    let post = indices(&meta.post_token_balances);
+    let lamports = |balances: &[u64], i: usize| balances.get(i).copied().unwrap_or(0);
+
+    let created = post.difference(&pre).filter(|&&i| i != own).map(|&i| {
+        match (
+            lamports(&meta.pre_balances, i),
+            lamports(&meta.post_balances, i),
+        ) {
+            (0, post) if is_token_account_rent(post, rent) => -(post as i128),
+            _ => 0,
+        }
+    });
+    let closed = pre.difference(&post).filter(|&&i| i != own).map(|&i| {
+        match (
+            lamports(&meta.pre_balances, i),
+            lamports(&meta.post_balances, i),
+        ) {
+            (pre, 0) if is_token_account_rent(pre, rent) => pre as i128,
+            _ => 0,
+        }
+    });
+    created.chain(closed).sum()
+}
+
+/// The account whose SOL balance moved the most in the opposite direction.
+fn sol_counterparty(
+    keys: &[ResolvedKey],
+    meta: &UiTransactionStatusMeta,
+    own: usize,
+    delta: i128,
+) -> Option<String> {
+    if delta == 0 {
+        return None;
+    }
+    (0..keys
+        .len()
+        .min(meta.pre_balances.len())
+        .min(meta.post_balances.len()))
+        .filter(|&i| i != own)
+        .map(|i| {
+            (
+                i,
+                meta.post_balances[i] as i128 - meta.pre_balances[i] as i128,
+            )
+        })
+        .filter(|(_, d)| d.signum() == -delta.signum())
+        .max_by_key(|(_, d)| d.abs())
+        .map(|(i, _)| keys[i].pubkey.clone())
+}
+
+/// Token balance changes of accounts owned by (or equal to) `address`.
+fn token_deltas(
+    meta: &UiTransactionStatusMeta,
+    keys: &[ResolvedKey],
+    address: &str,
+) -> Vec<TokenDelta> {
+    let empty = Vec::new();
+    let pre = match &meta.pre_token_balances {
+        OptionSerializer::Some(b) => b,
+        _ => &empty,
+    };
+    let post = match &meta.post_token_balances {
+        OptionSerializer::Some(b) => b,
+        _ => &empty,
+    };
+
+    // (account index) -> (mint, owner, decimals, pre, post)
+    let mut balances: BTreeMap<u8, (String, Option<String>, u8, i128, i128)> = BTreeMap::new();
+    let mut record = |b: &UiTransactionTokenBalance, is_post: bool| {
+        let amount = b.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+        let owner = match &b.owner {
+            OptionSerializer::Some(o) => Some(o.clone()),
+            _ => None,
+        };
+        let entry = balances.entry(b.account_index).or_insert((
+            b.mint.clone(),
+            owner,
+            b.ui_token_amount.decimals,
+            0,
+            0,
+        ));
+        if is_post {
+            entry.4 = amount;
+        } else {
+            entry.3 = amount;
+        }
+    };
+    pre.iter().for_each(|b| record(b, false));
+    post.iter().for_each(|b| record(b, true));
+
+    let is_ours = |index: u8, owner: &Option<String>| {
+        owner.as_deref() == Some(address)
+            || keys.get(index as usize).map(|k| k.pubkey.as_str()) == Some(address)
+    };
+
+    balances
+        .iter()
+        .filter(|(index, (_, owner, _, pre, post))| pre != post && is_ours(**index, owner))
+        .map(|(_, (mint, _, decimals, pre, post))| {
+            let delta = post - pre;
+            // The largest opposite move of the same mint by someone else
+            let counterparty = balances
+                .iter()
+                .filter(|(i, (m, owner, ..))| m == mint && !is_ours(**i, owner))
+                .map(|(i, (_, owner, _, p, q))| (i, owner, q - p))
+                .filter(|(_, _, d)| d.signum() == -delta.signum())
+                .max_by_key(|(_, _, d)| d.abs())
+                .and_then(|(i, owner, _)| {
+                    owner
+                        .clone()
+                        .or_else(|| keys.get(*i as usize).map(|k| k.pubkey.clone()))
+                });
+            TokenDelta {
+                mint: mint.clone(),
+                delta,
+                decimals: *decimals,
+                counterparty,
+            }
+        })
+        .collect()
+}
+
+/// Coarse classification of what a transaction did to the address.
+///
+/// `sol_net` is the SOL balance change with the fee added back, so a
+/// transaction that only paid fees nets to zero.
+fn classify(
+    keys: &[ResolvedKey],
+    failed: bool,
+    sol_net: i128,
+    tokens: &[TokenDelta],
+) -> &'static str {
+    if failed {
+        return "failed";
+    }
+    let involves = |program: &str| keys.iter().any(|k| k.pubkey == program);
+    if involves(VOTE_PROGRAM) {
+        return "vote";
+    }
+    if involves(STAKE_PROGRAM) {
+        return "stake";
+    }
+
+    let token_in = tokens.iter().any(|t| t.delta > 0);
+    let token_out = tokens.iter().any(|t| t.delta < 0);
+    if (token_in && (token_out || sol_net < 0)) || (token_out && sol_net > 0) {
+        return "swap";
+    }
+    match (token_in, token_out) {
+        (true, _) => "token-receive",
+        (_, true) => "token-send",
+        _ if sol_net > 0 => "receive",
+        _ if sol_net < 0 => "send",
+        _ => "fee",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    const WALLET: &str = "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin";
+    const OTHER: &str = "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T";
+    const ATA: &str = "C88XWfp26heEmDkmfSzeXP7Fd7GQJ2j9dDTUsyiZbUTa";
+    const OTHER_ATA: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xyybapC8G4wEGGkZwyTDt1v";
+    const OTHER_MINT: &str = "So11111111111111111111111111111111111111112";
+    const SYSTEM: &str = "11111111111111111111111111111111";
+    const FEE: u64 = 5000;
+    /// Rent-exempt minimum of a 165 byte SPL Token account.
+    const TOKEN_RENT: u64 = 2_039_280;
+    /// Rent-exempt minimum of a 170 byte Token-2022 account (ImmutableOwner).
+    const TOKEN_2022_RENT: u64 = 2_074_080;
+
+    fn balance(index: u8, mint: &str, owner: &str, amount: u64) -> Value {
+        json!({
+            "accountIndex": index,
+            "mint": mint,
+            "owner": owner,
+            "programId": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+            "uiTokenAmount": {
+                "uiAmount": null,
+                "decimals": 6,
+                "amount": amount.to_string(),
+                "uiAmountString": "0"
+            }
+        })
+    }
+
+    /// A confirmed transaction paid by `WALLET` over `keys` (plus the
+    /// System Program), with the given lamport and token balances.
+    fn tx(
+        keys: &[&str],
+        balances: (Vec<u64>, Vec<u64>),
+        tokens: (Vec<Value>, Vec<Value>),
+        err: Option<Value>,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        let mut account_keys: Vec<&str> = keys.to_vec();
+        account_keys.push(SYSTEM);
+        let (mut pre, mut post) = balances;
+        pre.push(1);
+        post.push(1);
+        serde_json::from_value(json!({
+            "slot": 10,
+            "blockTime": 1_700_000_000,
+            "transaction": {
+                "signatures": ["1".repeat(64)],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 1
+                    },
+                    "accountKeys": account_keys,
+                    "recentBlockhash": SYSTEM,
+                    "instructions": []
+                }
+            },
+            "meta": {
+                "err": err,
+                "status": { "Ok": null },
+                "fee": FEE,
+                "preBalances": pre,
+                "postBalances": post,
+                "preTokenBalances": tokens.0,
+                "postTokenBalances": tokens.1
+            }
+        }))
+        .unwrap()
+    }
+
+    fn actions(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<String> {
+        balance_rows(tx, WALLET, "sig", &Rent::default())
+            .into_iter()
+            .map(|row| row.action)
+            .collect()
+    }
+
+    #[test]
+    fn rent_matches_token_account_sizes() {
+        let rent = Rent::default();
+        assert!(is_token_account_rent(TOKEN_RENT, &rent));
+        assert!(is_token_account_rent(TOKEN_2022_RENT, &rent));
+        assert!(!is_token_account_rent(TOKEN_RENT + 1, &rent));
+        // Rent-exempt, but too small to be a token account
+        assert!(!is_token_account_rent(rent.minimum_balance(82), &rent));
+    }
+
+    #[test]
+    fn swap_moves_one_token_for_another() {
+        let tx = tx(
+            &[WALLET, ATA, OTHER_ATA],
+            (
+                vec![1_000_000, TOKEN_RENT, TOKEN_RENT],
+                vec![1_000_000 - FEE, TOKEN_RENT, TOKEN_RENT],
+            ),
+            (
+                vec![
+                    balance(1, MINT, WALLET, 10),
+                    balance(2, OTHER_MINT, WALLET, 0),
+                ],
+                vec![
+                    balance(1, MINT, WALLET, 0),
+                    balance(2, OTHER_MINT, WALLET, 7),
+                ],
+            ),
+            None,
+        );
+        assert_eq!(actions(&tx), ["swap", "swap", "swap"]);
+    }
+
+    #[test]
+    fn creating_token_2022_ata_to_receive_is_not_a_swap() {
+        let tx = tx(
+            &[WALLET, ATA, OTHER_ATA],
+            (
+                vec![10_000_000, 0, TOKEN_2022_RENT],
+                vec![
+                    10_000_000 - FEE - TOKEN_2022_RENT,
+                    TOKEN_2022_RENT,
+                    TOKEN_2022_RENT,
+                ],
+            ),
+            (
+                vec![balance(2, MINT, OTHER, 5)],
+                vec![balance(1, MINT, WALLET, 5), balance(2, MINT, OTHER, 0)],
+            ),
+            None,
+        );
+        let meta = tx.transaction.meta.as_ref().unwrap();
+        assert_eq!(
+            token_account_rent(meta, 0, &Rent::default()),
+            -(TOKEN_2022_RENT as i128)
+        );
+        let rows = balance_rows(&tx, WALLET, "sig", &Rent::default());
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|r| r.action == "token-receive"));
+        assert_eq!(rows[1].token_mint, MINT);
+        assert_eq!(rows[1].counterparty, OTHER);
+    }
+
+    #[test]
+    fn closing_token_account_refund_is_not_a_swap() {
+        let tx = tx(
+            &[WALLET, ATA, OTHER_ATA],
+            (
+                vec![1_000_000, TOKEN_RENT, TOKEN_RENT],
+                vec![1_000_000 - FEE + TOKEN_RENT, 0, TOKEN_RENT],
+            ),
+            (
+                vec![balance(1, MINT, WALLET, 5), balance(2, MINT, OTHER, 0)],
+                vec![balance(2, MINT, OTHER, 5)],
+            ),
+            None,
+        );
+        let meta = tx.transaction.meta.as_ref().unwrap();
+        assert_eq!(
+            token_account_rent(meta, 0, &Rent::default()),
+            TOKEN_RENT as i128
+        );
+        assert_eq!(actions(&tx), ["token-send", "token-send"]);
+    }
+
+    #[test]
+    fn sending_exactly_rent_to_a_new_wallet_is_a_send() {
+        let tx = tx(
+            &[WALLET, OTHER],
+            (
+                vec![10_000_000, 0],
+                vec![10_000_000 - FEE - TOKEN_RENT, TOKEN_RENT],
+            ),
+            (Vec::new(), Vec::new()),
+            None,
+        );
+        let meta = tx.transaction.meta.as_ref().unwrap();
+        assert_eq!(token_account_rent(meta, 0, &Rent::default()), 0);
+        let rows = balance_rows(&tx, WALLET, "sig", &Rent::default());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].action, "send");
+        assert_eq!(rows[0].counterparty, OTHER);
+        let keys = transaction::account_keys(&tx);
+        assert_eq!(
+            sol_counterparty(&keys, meta, 0, -((FEE + TOKEN_RENT) as i128)).as_deref(),
+            Some(OTHER)
+        );
+    }
+
+    #[test]
+    fn fee_only_transaction() {
+        let tx = tx(
+            &[WALLET],
+            (vec![1_000_000], vec![1_000_000 - FEE]),
+            (Vec::new(), Vec::new()),
+            None,
+        );
+        let rows = balance_rows(&tx, WALLET, "sig", &Rent::default());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].action, "fee");
+        assert_eq!(rows[0].sol_delta, utils::format_sol(-(FEE as i128)));
+        assert_eq!(rows[0].fee_paid, utils::format_sol(FEE as i128));
+    }
+
+    #[test]
+    fn failed_transaction() {
+        let tx = tx(
+            &[WALLET, OTHER],
+            (vec![1_000_000, 0], vec![1_000_000 - FEE, 0]),
+            (Vec::new(), Vec::new()),
+            Some(json!({ "InstructionError": [0, "InvalidArgument"] })),
+        );
+        assert_eq!(actions(&tx), ["failed"]);
+    }
+
+    #[test]
+    fn token_deltas_only_cover_own_accounts() {
+        let tx = tx(
+            &[WALLET, ATA, OTHER_ATA],
+            (vec![1, 1, 1], vec![1, 1, 1]),
+            (
+                vec![balance(1, MINT, WALLET, 9), balance(2, MINT, OTHER, 1)],
+                vec![balance(1, MINT, WALLET, 4), balance(2, MINT, OTHER, 6)],
+            ),
+            None,
+        );
+        let meta = tx.transaction.meta.as_ref().unwrap();
+        let keys = transaction::account_keys(&tx);
+        let deltas = token_deltas(meta, &keys, WALLET);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].mint, MINT);
+        assert_eq!(deltas[0].delta, -5);
+        assert_eq!(deltas[0].counterparty.as_deref(), Some(OTHER));
+        assert_eq!(classify(&keys, false, 0, &deltas), "token-send");
+    }
+}
@@ -0,0 +1,231 @@
+use crate::commands::cluster;
+use crate::rpc;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::clock::Slot;
+use solana_sdk::epoch_schedule::EpochSchedule;
+use solana_sdk::feature;
+use solana_sdk::feature_set::FEATURE_NAMES;
+use solana_sdk::pubkey::Pubkey;
+
+/// getMultipleAccounts accepts at most 100 addresses per call.
+const BATCH_SIZE: usize = 100;
+/// Description characters shown in tables.
+const DESCRIPTION_WIDTH: usize = 60;
+
+/// Activation state of a feature gate on a cluster.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FeatureStatus {
+    Active(Slot),
+    /// Activation was requested; it takes effect at the next epoch boundary.
+    Pending,
+    Inactive,
+}
+
+/// Status filter for `cluster-features`.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatusFilter {
+    Active,
+    Pending,
+    Inactive,
+}
+
+impl FeatureStatus {
+    fn matches(self, filter: StatusFilter) -> bool {
+        matches!(
+            (self, filter),
+            (FeatureStatus::Active(_), StatusFilter::Active)
+                | (FeatureStatus::Pending, StatusFilter::Pending)
+                | (FeatureStatus::Inactive, StatusFilter::Inactive)
+        )
+    }
+
+    fn label(self, schedule: &EpochSchedule) -> ColoredString {
+        match self {
+            FeatureStatus::Active(slot) => format!(
+                "active since slot {} (epoch {})",
+                slot,
+                schedule.get_epoch(slot)
+            )
+            .green(),
+            FeatureStatus::Pending => "pending activation".yellow(),
+            FeatureStatus::Inactive => "inactive".red(),
+        }
+    }
+}
+
+/// Command-line options for `cluster-features`.
+#[derive(clap::Args)]
+pub struct FeatureArgs {
+    /// Only list features with this status.
+    #[arg(long, value_enum)]
+    pub status: Option<StatusFilter>,
+
+    /// Only list features whose id or description contains this text.
+    #[arg(long)]
+    pub search: Option<String>,
+
+    /// Compare against another cluster (name or RPC URL) and list differences.
+    #[arg(long)]
+    pub diff: Option<String>,
+}
+
+/// Known features sorted by id, so output is stable.
+fn known_features() -> Vec<(Pubkey, &'static str)> {
+    let mut features: Vec<(Pubkey, &'static str)> = FEATURE_NAMES
+        .iter()
+        .map(|(id, name)| (*id, *name))
+        .collect();
+    features.sort_by_key(|(id, _)| id.to_string());
+    features
+}
+
+/// Fetch the feature accounts for `ids` in batches.
+fn fetch_statuses(client: &RpcClient, ids: &[Pubkey]) -> Result<Vec<FeatureStatus>> {
+    let mut statuses = Vec::with_capacity(ids.len());
+    for chunk in ids.chunks(BATCH_SIZE) {
+        for account in client.get_multiple_accounts(chunk)? {
+            let status = match account.as_ref().and_then(feature::from_account) {
+                Some(feature) => match feature.activated_at {
+                    Some(slot) => FeatureStatus::Active(slot),
+                    None => FeatureStatus::Pending,
+                },
+                None => FeatureStatus::Inactive,
+            };
+            statuses.push(status);
+        }
+    }
+    Ok(statuses)
+}
+
+fn short(description: &str) -> String {
+    if description.chars().count() > DESCRIPTION_WIDTH {
+        let cut: String = description.chars().take(DESCRIPTION_WIDTH - 1).collect();
+        format!("{}…", cut)
+    } else {
+        description.to_string()
+    }
+}
+
+/// List the activation status of every feature gate known to this build.
+///
+/// Features unknown to the bundled solana-sdk (newer than it) are not
+/// listed. With `--diff`, only features whose status differs between the
+/// two clusters are shown.
+pub fn list(client: &RpcClient, args: &FeatureArgs) -> Result<()> {
+    let features = known_features();
+    let ids: Vec<Pubkey> = features.iter().map(|(id, _)| *id).collect();
+    println!("Fetching {} feature accounts...", ids.len());
+    let statuses = fetch_statuses(client, &ids)?;
+    let schedule = client.get_epoch_schedule()?;
+
+    let search = args.search.as_deref().map(str::to_lowercase);
+    let selected = |i: usize| {
+        let (id, name) = &features[i];
+        let status_ok = args.status.is_none_or(|f| statuses[i].matches(f));
+        let search_ok = search.as_deref().is_none_or(|s| {
+            id.to_string().to_lowercase().contains(s) || name.to_lowercase().contains(s)
+        });
+        status_ok && search_ok
+    };
+
+    if let Some(other) = &args.diff {
+        let other_url = cluster::resolve_url(other)?;
+        let other_client = rpc::get_client(&other_url);
+        let other_statuses = fetch_statuses(&other_client, &ids)?;
+        let other_schedule = other_client.get_epoch_schedule()?;
+
+        let differing: Vec<usize> = (0..features.len())
+            .filter(|&i| selected(i))
+            .filter(|&i| {
+                // Activation slots differ between clusters; compare the state
+                std::mem::discriminant(&statuses[i]) != std::mem::discriminant(&other_statuses[i])
+            })
+            .collect();
+        println!(
+            "\n{}",
+            format!(
+                "--- Feature Differences vs {} ({}) ---",
+                other,
+                differing.len()
+            )
+            .bold()
+            .cyan()
+        );
+        if differing.is_empty() {
+            println!(
+                "{}",
+                "Both clusters have the same feature gates active.".green()
+            );
+            return Ok(());
+        }
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("Feature").style_spec("Fy"),
+            Cell::new("Description").style_spec("Fc"),
+            Cell::new("This Cluster").style_spec("Fm"),
+            Cell::new(other).style_spec("Fm"),
+        ]));
+        for i in differing {
+            table.add_row(Row::new(vec![
+                Cell::new(&features[i].0.to_string()),
+                Cell::new(&short(features[i].1)),
+                Cell::new(&statuses[i].label(&schedule).to_string()),
+                Cell::new(&other_statuses[i].label(&other_schedule).to_string()),
+            ]));
+        }
+        table.printstd();
+        return Ok(());
+    }
+
+    // Pending first, then inactive, then most recently activated
+    let mut order: Vec<usize> = (0..features.len()).filter(|&i| selected(i)).collect();
+    order.sort_by_key(|&i| match statuses[i] {
+        FeatureStatus::Pending => (0, 0),
+        FeatureStatus::Inactive => (1, 0),
+        FeatureStatus::Active(slot) => (2, u64::MAX - slot),
+    });
+
+    let count = |status: fn(&FeatureStatus) -> bool| statuses.iter().filter(|s| status(s)).count();
+    println!("\n{}", "--- Feature Gates ---".bold().cyan());
+    println!("{:<25} : {}", "Known Features", features.len());
+    println!(
+        "{:<25} : {}",
+        "Active",
+        count(|s| matches!(s, FeatureStatus::Active(_)))
+            .to_string()
+            .green()
+    );
+    println!(
+        "{:<25} : {}",
+        "Pending",
+        count(|s| *s == FeatureStatus::Pending).to_string().yellow()
+    );
+    println!(
+        "{:<25} : {}",
+        "Inactive",
+        count(|s| *s == FeatureStatus::Inactive).to_string().red()
+    );
+
+    if order.is_empty() {
+        println!("No features match the filters.");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Feature").style_spec("Fy"),
+        Cell::new("Description").style_spec("Fc"),
+        Cell::new("Status").style_spec("Fm"),
+    ]));
+    for i in order {
+        table.add_row(Row::new(vec![
+            Cell::new(&features[i].0.to_string()),
+            Cell::new(&short(features[i].1)),
+            Cell::new(&statuses[i].label(&schedule).to_string()),
+        ]));
+    }
+    table.printstd();
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use crate::commands::{
+    account, block, cluster, lookup_table, program, stake, token, transaction, validator,
+};
+use crate::decode::{self, AccountKind};
+use crate::labels;
+use crate::rpc;
+use anyhow::Result;
+use colored::*;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::str::FromStr;
+
+/// What an `inspect` argument refers to.
+#[derive(Debug, PartialEq)]
+enum Target {
+    Signature(String),
+    Slot(u64),
+    Address(String),
+}
+
+/// Inspect anything: a signature, slot, address or explorer URL.
+///
+/// Addresses are dispatched on their owner program to the matching
+/// detailed view (mint, token account, stake, vote, program, lookup
+/// table, nonce). Explorer URLs that name a cluster are queried against
+/// that cluster instead of the configured one.
+pub fn inspect(client: &RpcClient, input: &str) -> Result<()> {
+    let input = input.trim();
+    let (target, url_cluster) = match parse_url(input) {
+        Some((target, cluster)) => (target, cluster),
+        None => (classify(input)?, None),
+    };
+
+    let url_client;
+    let client = match url_cluster {
+        Some(url) => {
+            println!("Using cluster from URL: {}", url.cyan());
+            url_client = rpc::get_client(&url);
+            &url_client
+        }
+        None => client,
+    };
+
+    match target {
+        Target::Signature(sig) => {
+            println!("Detected: {}", "Transaction Signature".green());
+            transaction::get_details(client, &sig)
+        }
+        Target::Slot(slot) => {
+            println!("Detected: {}", "Slot".green());
+            block::get_block(client, slot)
+        }
+        Target::Address(address) => inspect_address(client, &address),
+    }
+}
+
+fn inspect_address(client: &RpcClient, address: &str) -> Result<()> {
+    let pubkey = Pubkey::from_str(address)?;
+    let Some(account) = client
+        .get_account_with_commitment(&pubkey, client.commitment())?
+        .value
+    else {
+        println!("Detected: {}", "Address".green());
+        println!(
+            "{}",
+            "Account does not exist (no lamports or data) on this cluster.".yellow()
+        );
+        return Ok(());
+    };
+
+    let kind = decode::kind(&account);
+    println!(
+        "Detected: {} (owner: {})",
+        kind.name().green(),
+        labels::display(&account.owner.to_string())
+    );
+    match kind {
+        AccountKind::Mint => token::get_mint_info(client, address),
+        AccountKind::TokenAccount => token::get_token_account(client, address),
+        AccountKind::Stake => stake::get_stake_account(client, address),
+        AccountKind::Vote => validator::get_vote_account(client, address),
+        AccountKind::Program => program::get_info(client, address),
+        AccountKind::LookupTable => lookup_table::get_info(client, address),
+        AccountKind::Nonce => account::get_nonce(client, address),
+        AccountKind::Other => account::get_info(client, address, &Default::default()),
+    }
+}
+
+/// Recognize a bare signature, slot number or address.
+fn classify(input: &str) -> Result<Target> {
+    if let Ok(slot) = input.parse::<u64>() {
+        return Ok(Target::Slot(slot));
+    }
+    if Signature::from_str(input).is_ok() {
+        return Ok(Target::Signature(input.to_string()));
+    }
+    if Pubkey::from_str(input).is_ok() {
+        return Ok(Target::Address(input.to_string()));
+    }
+    anyhow::bail!(
+        "Could not recognize '{}' as a signature, slot, address or explorer URL",
+        input
+    )
+}
+
+/// Parse explorer.solana.com, solscan.io, solana.fm and similar URLs.
+///
+/// Returns the target and, when the URL selects a cluster, its RPC URL.
+fn parse_url(input: &str) -> Option<(Target, Option<String>)> {
+    let rest = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))?;
+    let (path, query) = match rest.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (rest, ""),
+    };
+    let path = path.split('#').next().unwrap_or(path);
+    let mut segments = path.split('/').skip(1).filter(|s| !s.is_empty());
+    let kind = segments.next()?;
+    let value = segments.next()?;
+
+    let target = match kind {
+        "tx" | "transaction" => Target::Signature(value.to_string()),
+        "block" => Target::Slot(value.parse().ok()?),
+        "address" | "account" | "token" => Target::Address(value.to_string()),
+        _ => return None,
+    };
+
+    let mut cluster = None;
+    for pair in query.split('&') {
+        match pair.split_once('=') {
+            Some(("customUrl", url)) => cluster = Some(percent_decode(url)),
+            Some(("cluster", name)) if cluster.is_none() => {
+                // solana.fm uses "devnet-solana" / "mainnet-alpha"
+                let name = name.split('-').next().unwrap_or(name);
+                if name != "custom" {
+                    cluster = Some(cluster::url_for(name).to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    Some((target, cluster))
+}
+
+/// Minimal percent-decoding for `customUrl` query values.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = input
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
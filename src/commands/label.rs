@@ -0,0 +1,67 @@
+use crate::config::Config;
+use crate::labels;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+pub fn add(cfg: &mut Config, address: String, name: String) -> Result<()> {
+    Pubkey::from_str(&address).map_err(|_| anyhow::anyhow!("Invalid Address"))?;
+    if name.trim().is_empty() {
+        anyhow::bail!("Label name cannot be empty");
+    }
+    cfg.labels.insert(address.clone(), name.clone());
+    cfg.save()?;
+    println!(
+        "{} Labelled {} as {}",
+        "✔".green(),
+        address.yellow(),
+        name.cyan()
+    );
+    Ok(())
+}
+
+pub fn remove(cfg: &mut Config, address: String) -> Result<()> {
+    if cfg.labels.remove(&address).is_none() {
+        anyhow::bail!("No user label for {}", address);
+    }
+    cfg.save()?;
+    println!("{} Removed label for {}", "✔".green(), address.yellow());
+    Ok(())
+}
+
+pub fn list(cfg: &Config) -> Result<()> {
+    println!(
+        "\n{}",
+        format!("--- User Labels ({}) ---", cfg.labels.len())
+            .bold()
+            .cyan()
+    );
+    if cfg.labels.is_empty() {
+        println!("No user labels. Add one with `label-add <ADDRESS> <NAME>`.");
+    } else {
+        print_table(cfg.labels.iter().map(|(a, n)| (a.as_str(), n.as_str())));
+    }
+
+    println!(
+        "\n{}",
+        format!("--- Built-in Labels ({}) ---", labels::builtins().len())
+            .bold()
+            .cyan()
+    );
+    print_table(labels::builtins().iter().copied());
+    Ok(())
+}
+
+fn print_table<'a>(entries: impl Iterator<Item = (&'a str, &'a str)>) {
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Address").style_spec("Fy"),
+        Cell::new("Label").style_spec("Fc"),
+    ]));
+    for (address, name) in entries {
+        table.add_row(Row::new(vec![Cell::new(address), Cell::new(name)]));
+    }
+    table.printstd();
+}
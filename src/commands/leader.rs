@@ -0,0 +1,235 @@
+use crate::commands::network;
+use crate::labels;
+use crate::utils;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use solana_client::rpc_client::RpcClient;
+use std::collections::HashMap;
+
+/// Validators shown in the leader-slot share table.
+const SHARE_ROWS: usize = 20;
+
+/// Consecutive slots led by one validator, as epoch slot indexes.
+struct Rotation<'a> {
+    leader: &'a str,
+    first: usize,
+    last: usize,
+}
+
+/// Split a sorted list of slot indexes into runs of consecutive slots.
+fn runs(slots: &[usize]) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for &slot in slots {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == slot => *last = slot,
+            _ => runs.push((slot, slot)),
+        }
+    }
+    runs
+}
+
+/// Show upcoming leaders, or every leader slot of one validator, for the
+/// current epoch.
+///
+/// Timing is estimated from the average slot time of recent performance
+/// samples, so estimates drift further out in the epoch.
+pub fn show(client: &RpcClient, count: usize, validator: Option<&str>) -> Result<()> {
+    let epoch_info = client.get_epoch_info()?;
+    let schedule = client
+        .get_leader_schedule(None)?
+        .ok_or_else(|| anyhow::anyhow!("No leader schedule available for the current epoch"))?;
+    let slot_secs = network::average_slot_time(client)?;
+    let first_slot = epoch_info.absolute_slot - epoch_info.slot_index;
+    let current = epoch_info.slot_index as usize;
+    let now = chrono::Utc::now().timestamp();
+
+    // Estimated (seconds from now, unix time) at which an epoch slot index starts
+    let eta = |index: usize| {
+        let secs = (index as f64 - current as f64) * slot_secs;
+        (secs, now + secs.round() as i64)
+    };
+
+    println!("\n{}", "--- Leader Schedule ---".bold().cyan());
+    println!(
+        "{:<25} : {}",
+        "Epoch",
+        epoch_info.epoch.to_string().yellow()
+    );
+    println!(
+        "{:<25} : {} ({} / {} in epoch)",
+        "Current Slot",
+        epoch_info.absolute_slot.to_string().green(),
+        epoch_info.slot_index,
+        epoch_info.slots_in_epoch
+    );
+    println!("{:<25} : {:.0} ms", "Avg Slot Time", slot_secs * 1000.0);
+
+    if let Some(identity) = validator {
+        let mut slots = schedule.get(identity).cloned().unwrap_or_default();
+        slots.sort_unstable();
+        let done = slots.iter().filter(|&&s| s < current).count();
+        println!(
+            "\n{}",
+            format!("--- Leader Slots: {} ---", labels::display(identity))
+                .bold()
+                .cyan()
+        );
+        println!("{:<25} : {}", "Leader Slots", slots.len());
+        println!(
+            "{:<25} : {:.2}%",
+            "Share of Epoch",
+            slots.len() as f64 / epoch_info.slots_in_epoch as f64 * 100.0
+        );
+        println!("{:<25} : {}", "Completed", done);
+        println!("{:<25} : {}", "Remaining", slots.len() - done);
+        if slots.is_empty() {
+            println!("{}", "Validator has no leader slots this epoch.".yellow());
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("Slots").style_spec("Fy"),
+            Cell::new("Status").style_spec("Fc"),
+            Cell::new("ETA").style_spec("Fg"),
+            Cell::new("Est. Time").style_spec("Fm"),
+        ]));
+        for (first, last) in runs(&slots) {
+            let (secs, at) = eta(first);
+            let (status, when) = if last < current {
+                (
+                    "Done".normal(),
+                    format!("{} ago", utils::format_duration(-secs)),
+                )
+            } else if first <= current {
+                ("Leading".green().bold(), "now".to_string())
+            } else {
+                (
+                    "Upcoming".yellow(),
+                    format!("in {}", utils::format_duration(secs)),
+                )
+            };
+            table.add_row(Row::new(vec![
+                Cell::new(&slot_range(first_slot, first, last)),
+                Cell::new(&status.to_string()),
+                Cell::new(&when),
+                Cell::new(&utils::format_utc(at)),
+            ]));
+        }
+        table.printstd();
+        return Ok(());
+    }
+
+    // Epoch slot index -> leader identity
+    let mut leaders: Vec<Option<&str>> = vec![None; epoch_info.slots_in_epoch as usize];
+    for (identity, slots) in &schedule {
+        for &slot in slots {
+            if let Some(entry) = leaders.get_mut(slot) {
+                *entry = Some(identity.as_str());
+            }
+        }
+    }
+    let mut rotations: Vec<Rotation> = Vec::new();
+    for (index, leader) in leaders.iter().enumerate().skip(current) {
+        let Some(leader) = *leader else { continue };
+        if let Some(r) = rotations.last_mut()
+            && r.leader == leader
+            && r.last + 1 == index
+        {
+            r.last = index;
+            continue;
+        }
+        if rotations.len() == count {
+            break;
+        }
+        rotations.push(Rotation {
+            leader,
+            first: index,
+            last: index,
+        });
+    }
+
+    println!(
+        "\n{}",
+        format!("--- Next {} Leaders ---", rotations.len())
+            .bold()
+            .cyan()
+    );
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Slots").style_spec("Fy"),
+        Cell::new("Leader").style_spec("Fm"),
+        Cell::new("ETA").style_spec("Fg"),
+        Cell::new("Est. Time").style_spec("Fc"),
+    ]));
+    for r in &rotations {
+        let (secs, at) = eta(r.first);
+        let when = if r.first <= current {
+            "now".to_string()
+        } else {
+            format!("in {}", utils::format_duration(secs))
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&slot_range(first_slot, r.first, r.last)),
+            Cell::new(&labels::display(r.leader)),
+            Cell::new(&when),
+            Cell::new(&utils::format_utc(at)),
+        ]));
+    }
+    table.printstd();
+    if rotations.len() < count {
+        println!(
+            "{}",
+            "Schedule for the next epoch is not yet available.".yellow()
+        );
+    }
+
+    print_shares(&schedule, epoch_info.slots_in_epoch);
+    Ok(())
+}
+
+/// Print the validators with the most leader slots this epoch.
+fn print_shares(schedule: &HashMap<String, Vec<usize>>, slots_in_epoch: u64) {
+    let mut shares: Vec<(&String, usize)> = schedule
+        .iter()
+        .map(|(id, slots)| (id, slots.len()))
+        .collect();
+    shares.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    println!(
+        "\n{}",
+        format!("--- Leader Slot Share ({} validators) ---", shares.len())
+            .bold()
+            .cyan()
+    );
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Identity").style_spec("Fm"),
+        Cell::new("Leader Slots").style_spec("Fy"),
+        Cell::new("Share").style_spec("Fg"),
+    ]));
+    for (identity, slots) in shares.iter().take(SHARE_ROWS) {
+        table.add_row(Row::new(vec![
+            Cell::new(&labels::display(identity)),
+            Cell::new(&slots.to_string()),
+            Cell::new(&format!(
+                "{:.2}%",
+                *slots as f64 / slots_in_epoch as f64 * 100.0
+            )),
+        ]));
+    }
+    table.printstd();
+    if shares.len() > SHARE_ROWS {
+        println!("... and {} more.", shares.len() - SHARE_ROWS);
+    }
+}
+
+/// Absolute slot range for epoch slot indexes `first..=last`.
+fn slot_range(first_slot: u64, first: usize, last: usize) -> String {
+    if first == last {
+        (first_slot + first as u64).to_string()
+    } else {
+        format!("{}-{}", first_slot + first as u64, first_slot + last as u64)
+    }
+}
@@ -0,0 +1,326 @@
+use crate::labels;
+use crate::rpc;
+use anyhow::Result;
+use colored::*;
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Colors cycled through to tell programs apart in nested logs.
+const PROGRAM_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Blue,
+    Color::Yellow,
+    Color::BrightGreen,
+    Color::BrightMagenta,
+];
+
+/// A single runtime log message, classified.
+pub enum LogEntry {
+    /// `Program <id> invoke [depth]`
+    Invoke { program: String, depth: usize },
+    /// `Program <id> success`
+    Success { program: String },
+    /// `Program <id> failed: <error>`
+    Failed { program: String, error: String },
+    /// `Program <id> consumed <used> of <limit> compute units`
+    Consumed { used: u64, limit: u64 },
+    /// `Program log: <message>`
+    Log(String),
+    /// `Program data: <base64>`
+    Data(String),
+    /// `Program return: <id> <base64>`
+    Return { program: String, data: String },
+    /// Anything else, such as `Log truncated`.
+    Other(String),
+}
+
+/// A log entry with the invocation depth and program that emitted it.
+pub struct LogLine {
+    /// 1 for top-level instructions, 0 outside of any invocation.
+    pub depth: usize,
+    /// The program executing when the line was logged.
+    pub program: Option<String>,
+    pub entry: LogEntry,
+}
+
+/// Parse one log message.
+pub fn parse_entry(line: &str) -> LogEntry {
+    if let Some(msg) = line.strip_prefix("Program log: ") {
+        return LogEntry::Log(msg.to_string());
+    }
+    if let Some(data) = line.strip_prefix("Program data: ") {
+        return LogEntry::Data(data.to_string());
+    }
+    if let Some(rest) = line.strip_prefix("Program return: ")
+        && let Some((program, data)) = rest.split_once(' ')
+    {
+        return LogEntry::Return {
+            program: program.to_string(),
+            data: data.to_string(),
+        };
+    }
+    let Some((program, rest)) = line
+        .strip_prefix("Program ")
+        .and_then(|rest| rest.split_once(' '))
+    else {
+        return LogEntry::Other(line.to_string());
+    };
+    let program = program.to_string();
+    if let Some(depth) = rest
+        .strip_prefix("invoke [")
+        .and_then(|d| d.strip_suffix(']'))
+        .and_then(|d| d.parse().ok())
+    {
+        return LogEntry::Invoke { program, depth };
+    }
+    if rest == "success" {
+        return LogEntry::Success { program };
+    }
+    if let Some(error) = rest.strip_prefix("failed: ") {
+        return LogEntry::Failed {
+            program,
+            error: error.to_string(),
+        };
+    }
+    if let Some((used, limit)) = rest
+        .strip_prefix("consumed ")
+        .and_then(|r| r.strip_suffix(" compute units"))
+        .and_then(|r| r.split_once(" of "))
+        && let (Ok(used), Ok(limit)) = (used.parse(), limit.parse())
+    {
+        return LogEntry::Consumed { used, limit };
+    }
+    LogEntry::Other(line.to_string())
+}
+
+/// Parse the log messages of a transaction, tracking the invocation stack.
+pub fn parse_logs(logs: &[String]) -> Vec<LogLine> {
+    let mut stack: Vec<String> = Vec::new();
+    let mut lines = Vec::with_capacity(logs.len());
+    for log in logs {
+        let entry = parse_entry(log);
+        if let LogEntry::Invoke { program, .. } = &entry {
+            stack.push(program.clone());
+        }
+        let returned = matches!(entry, LogEntry::Success { .. } | LogEntry::Failed { .. });
+        lines.push(LogLine {
+            depth: stack.len(),
+            program: stack.last().cloned(),
+            entry,
+        });
+        if returned {
+            stack.pop();
+        }
+    }
+    lines
+}
+
+/// A stable color for a program, so it looks the same across transactions.
+fn program_color(program: &str) -> Color {
+    let hash = program
+        .bytes()
+        .fold(0usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+    PROGRAM_COLORS[hash % PROGRAM_COLORS.len()]
+}
+
+/// Print parsed logs indented by invocation depth, colored per program.
+pub fn print_logs(logs: &[String]) {
+    for line in parse_logs(logs) {
+        let indent = "  ".repeat(line.depth.saturating_sub(1));
+        let color = line.program.as_deref().map_or(Color::White, program_color);
+        let text = match &line.entry {
+            LogEntry::Invoke { program, depth } => format!(
+                "{} {} [{}]",
+                "▶".color(color),
+                labels::display(program).color(color).bold(),
+                depth
+            ),
+            LogEntry::Success { program } => format!(
+                "{} {} {}",
+                "✔".green(),
+                labels::display(program).color(color),
+                "success".green()
+            ),
+            LogEntry::Failed { program, error } => format!(
+                "{} {} {}",
+                "✘".red().bold(),
+                labels::display(program).color(color),
+                format!("failed: {}", error).red().bold()
+            ),
+            LogEntry::Consumed { used, limit } => format!(
+                "  {}",
+                format!("consumed {} of {} CU", used, limit).dimmed()
+            ),
+            LogEntry::Log(msg) => format!("  {} {}", "│".color(color), msg),
+            LogEntry::Data(data) => format!("  {} data: {}", "│".color(color), data.dimmed()),
+            LogEntry::Return { program, data } => format!(
+                "  {} return from {}: {}",
+                "│".color(color),
+                labels::display(program),
+                data.dimmed()
+            ),
+            LogEntry::Other(text) => format!("  {}", text.dimmed()),
+        };
+        println!("{}{}", indent, text);
+    }
+}
+
+/// Stream transaction logs over a `logsSubscribe` websocket.
+///
+/// Follows transactions mentioning `mentions`, or all non-vote transactions
+/// when it is `None`. With `errors_only`, successful transactions are
+/// skipped. Stops after `count` printed transactions when given.
+pub fn watch(
+    rpc_url: &str,
+    mentions: Option<&str>,
+    errors_only: bool,
+    count: Option<usize>,
+) -> Result<()> {
+    let filter = match mentions {
+        Some(address) => {
+            Pubkey::from_str(address).map_err(|_| anyhow::anyhow!("Invalid address"))?;
+            RpcTransactionLogsFilter::Mentions(vec![address.to_string()])
+        }
+        None => RpcTransactionLogsFilter::All,
+    };
+    let config = RpcTransactionLogsConfig {
+        commitment: Some(CommitmentConfig::confirmed()),
+    };
+    let ws_url = rpc::ws_url(rpc_url);
+    let (subscription, receiver) = PubsubClient::logs_subscribe(&ws_url, filter, config)
+        .map_err(|e| anyhow::anyhow!("Failed to subscribe via {}: {}", ws_url, e))?;
+
+    println!("\n{}", "--- Watching Logs ---".bold().cyan());
+    let target = mentions.map_or("All transactions (excluding votes)".to_string(), |a| {
+        labels::display(a)
+    });
+    println!("{:<25} : {}", "Filter", target.yellow());
+    if errors_only {
+        println!("{:<25} : {}", "Showing", "Failed transactions only".red());
+    }
+    println!("{:<25} : {}", "WebSocket", ws_url);
+    println!(
+        "{}",
+        "Waiting for transactions (Ctrl+C to stop)...".dimmed()
+    );
+
+    let mut shown = 0usize;
+    while count.is_none_or(|n| shown < n) {
+        let Ok(update) = receiver.recv() else {
+            rpc::close_subscription(subscription);
+            anyhow::bail!("WebSocket connection closed");
+        };
+        let logs = update.value;
+        if errors_only && logs.err.is_none() {
+            continue;
+        }
+        let status = match &logs.err {
+            Some(err) => format!("FAILED ({:?})", err).red().bold(),
+            None => "SUCCESS".green().bold(),
+        };
+        println!(
+            "\n[{}] slot {}  {}  {}",
+            chrono::Utc::now().format("%H:%M:%S"),
+            update.context.slot.to_string().cyan(),
+            logs.signature.yellow(),
+            status
+        );
+        print_logs(&logs.logs);
+        shown += 1;
+    }
+    rpc::close_subscription(subscription);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+
+    #[test]
+    fn parse_entry_classifies_runtime_lines() {
+        assert!(matches!(
+            parse_entry(&format!("Program {} invoke [2]", TOKEN)),
+            LogEntry::Invoke { program, depth: 2 } if program == TOKEN
+        ));
+        assert!(matches!(
+            parse_entry(&format!("Program {} success", TOKEN)),
+            LogEntry::Success { program } if program == TOKEN
+        ));
+        assert!(matches!(
+            parse_entry(&format!("Program {} failed: custom program error: 0x1", TOKEN)),
+            LogEntry::Failed { program, error }
+                if program == TOKEN && error == "custom program error: 0x1"
+        ));
+        assert!(matches!(
+            parse_entry(&format!(
+                "Program {} consumed 4645 of 200000 compute units",
+                TOKEN
+            )),
+            LogEntry::Consumed {
+                used: 4645,
+                limit: 200000
+            }
+        ));
+        assert!(matches!(
+            parse_entry("Program log: Instruction: Transfer"),
+            LogEntry::Log(msg) if msg == "Instruction: Transfer"
+        ));
+        assert!(matches!(
+            parse_entry("Program data: AQID"),
+            LogEntry::Data(data) if data == "AQID"
+        ));
+        assert!(matches!(
+            parse_entry(&format!("Program return: {} AQAAAA==", JUPITER)),
+            LogEntry::Return { program, data } if program == JUPITER && data == "AQAAAA=="
+        ));
+        assert!(matches!(parse_entry("Log truncated"), LogEntry::Other(_)));
+        assert!(matches!(
+            parse_entry(&format!("Program {} invoke [x]", TOKEN)),
+            LogEntry::Other(_)
+        ));
+    }
+
+    #[test]
+    fn parse_logs_tracks_nested_invocations() {
+        let logs: Vec<String> = [
+            format!("Program {} invoke [1]", JUPITER),
+            "Program log: Instruction: Route".to_string(),
+            format!("Program {} invoke [2]", TOKEN),
+            "Program log: Instruction: Transfer".to_string(),
+            format!("Program {} consumed 4645 of 180000 compute units", TOKEN),
+            format!("Program {} success", TOKEN),
+            "Program data: AQID".to_string(),
+            format!("Program {} consumed 30000 of 200000 compute units", JUPITER),
+            format!("Program {} failed: custom program error: 0x1", JUPITER),
+            "Log truncated".to_string(),
+        ]
+        .into();
+        let lines = parse_logs(&logs);
+        let trace: Vec<(usize, Option<&str>)> = lines
+            .iter()
+            .map(|l| (l.depth, l.program.as_deref()))
+            .collect();
+        assert_eq!(
+            trace,
+            [
+                (1, Some(JUPITER)),
+                (1, Some(JUPITER)),
+                (2, Some(TOKEN)),
+                (2, Some(TOKEN)),
+                (2, Some(TOKEN)),
+                (2, Some(TOKEN)),
+                (1, Some(JUPITER)),
+                (1, Some(JUPITER)),
+                (1, Some(JUPITER)),
+                (0, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::labels;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::address_lookup_table::{self, state::AddressLookupTable};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Fetch and decode an Address Lookup Table account.
+///
+/// Displays the table authority, deactivation slot, last extension
+/// details and every stored address with its index.
+pub fn get_info(client: &RpcClient, address_str: &str) -> Result<()> {
+    let pubkey = Pubkey::from_str(address_str)
+        .map_err(|_| anyhow::anyhow!("Invalid Lookup Table address"))?;
+    let account = client.get_account(&pubkey)?;
+
+    if account.owner != address_lookup_table::program::id() {
+        anyhow::bail!(
+            "Account is not an Address Lookup Table (owner: {})",
+            account.owner
+        );
+    }
+
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode lookup table: {}", e))?;
+    let meta = &table.meta;
+
+    println!("\n{}", "--- Address Lookup Table ---".bold().cyan());
+    println!("{:<25} : {}", "Address", address_str.yellow());
+    match meta.authority {
+        Some(authority) => println!(
+            "{:<25} : {}",
+            "Authority",
+            labels::display(&authority.to_string()).magenta()
+        ),
+        None => println!("{:<25} : {}", "Authority", "None (Frozen)".yellow()),
+    }
+    if meta.deactivation_slot == u64::MAX {
+        println!("{:<25} : {}", "Deactivation Slot", "Active".green());
+    } else {
+        println!(
+            "{:<25} : {}",
+            "Deactivation Slot",
+            meta.deactivation_slot.to_string().red()
+        );
+    }
+    println!("{:<25} : {}", "Last Extended Slot", meta.last_extended_slot);
+    println!(
+        "{:<25} : {}",
+        "Last Extended Start Index", meta.last_extended_slot_start_index
+    );
+    println!(
+        "{:<25} : {:.9} SOL",
+        "Balance",
+        account.lamports as f64 / 1_000_000_000.0
+    );
+
+    println!(
+        "\n{}",
+        format!("--- Addresses ({}) ---", table.addresses.len())
+            .bold()
+            .cyan()
+    );
+    if table.addresses.is_empty() {
+        println!("Table is empty.");
+        return Ok(());
+    }
+
+    let mut rows = Table::new();
+    rows.add_row(Row::new(vec![
+        Cell::new("Index").style_spec("Fm"),
+        Cell::new("Address").style_spec("Fy"),
+    ]));
+    for (i, address) in table.addresses.iter().enumerate() {
+        rows.add_row(Row::new(vec![
+            Cell::new(&i.to_string()),
+            Cell::new(&labels::display(&address.to_string())),
+        ]));
+    }
+    rows.printstd();
+
+    Ok(())
+}
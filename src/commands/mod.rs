@@ -1,10 +1,24 @@
 pub mod account;
+pub mod account_watch;
+pub mod bench;
 pub mod block;
 pub mod cluster;
-pub mod global; // Can keep or deprecate if merged to network, but keeping file is fine.
+pub mod export;
+pub mod features;
+pub mod inspect;
+pub mod label;
+pub mod leader;
+pub mod logs;
+pub mod lookup_table;
 pub mod network;
+pub mod portfolio;
 pub mod program;
+pub mod rent;
+pub mod rewards;
 pub mod stake;
+pub mod sysvar;
 pub mod token;
 pub mod transaction;
+pub mod tx_watch;
 pub mod validator;
+pub mod watch;
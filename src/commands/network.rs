@@ -1,17 +1,40 @@
+use crate::commands::watch::Metrics;
+use crate::utils;
 use anyhow::Result;
 use colored::*;
 use solana_client::rpc_client::RpcClient;
+use solana_sdk::clock::{Slot, UnixTimestamp};
+
+/// Nominal slot duration, used when the node has no performance samples.
+pub const DEFAULT_SLOT_SECS: f64 = 0.4;
+
+/// Average slot duration in seconds over the recent performance samples.
+pub fn average_slot_time(client: &RpcClient) -> Result<f64> {
+    let samples = client.get_recent_performance_samples(Some(60))?;
+    let slots: u64 = samples.iter().map(|s| s.num_slots).sum();
+    let secs: u64 = samples.iter().map(|s| s.sample_period_secs as u64).sum();
+    if slots == 0 {
+        return Ok(DEFAULT_SLOT_SECS);
+    }
+    Ok(secs as f64 / slots as f64)
+}
 
 /// Fetch and display general network status.
 ///
 /// Displays:
 /// - Cluster Version
-/// - Current Epoch and Progress percentage
+/// - Current Epoch, Progress percentage, remaining slots and ETA to the
+///   epoch end (from the average slot time of recent performance samples)
 /// - Current Slot and Block Height
-pub fn get_status(client: &RpcClient) -> Result<()> {
+///
+/// Returns the slot, block height, epoch progress and slot time for `--watch`.
+pub fn get_status(client: &RpcClient) -> Result<Metrics> {
     let epoch_info = client.get_epoch_info()?;
     let version = client.get_version()?;
     let block_height = client.get_block_height()?;
+    let slot_secs = average_slot_time(client)?;
+    let remaining = epoch_info.slots_in_epoch - epoch_info.slot_index;
+    let eta_secs = remaining as f64 * slot_secs;
 
     println!("\n{}", "--- Solana Network Status ---".bold().cyan());
     println!("{:<20} : {}", "Cluster Version", version.solana_core.cyan());
@@ -25,6 +48,17 @@ pub fn get_status(client: &RpcClient) -> Result<()> {
         "Epoch Progress",
         (epoch_info.slot_index as f64 / epoch_info.slots_in_epoch as f64 * 100.0)
     );
+    println!(
+        "{:<20} : {} of {}",
+        "Slots Remaining", remaining, epoch_info.slots_in_epoch
+    );
+    println!(
+        "{:<20} : {} ({})",
+        "Epoch Ends In",
+        utils::format_duration(eta_secs).yellow(),
+        utils::format_utc(chrono::Utc::now().timestamp() + eta_secs.round() as i64)
+    );
+    println!("{:<20} : {:.0} ms", "Avg Slot Time", slot_secs * 1000.0);
     println!(
         "{:<20} : {}",
         "Current Slot",
@@ -37,10 +71,20 @@ pub fn get_status(client: &RpcClient) -> Result<()> {
     );
     println!("{}\n", "-----------------------------".bold().cyan());
 
-    Ok(())
+    Ok(vec![
+        ("Slot", epoch_info.absolute_slot as f64),
+        ("Block Height", block_height as f64),
+        (
+            "Epoch Progress %",
+            ((epoch_info.slot_index as f64 / epoch_info.slots_in_epoch as f64) * 10_000.0).round()
+                / 100.0,
+        ),
+        ("Avg Slot Time ms", (slot_secs * 1000.0).round()),
+    ])
 }
 
-pub fn get_supply(client: &RpcClient) -> Result<()> {
+/// Show the SOL supply; returns the amounts in SOL for `--watch`.
+pub fn get_supply(client: &RpcClient) -> Result<Metrics> {
     let supply = client
         .supply_with_commitment(solana_sdk::commitment_config::CommitmentConfig::finalized())?
         .value;
@@ -64,7 +108,12 @@ pub fn get_supply(client: &RpcClient) -> Result<()> {
             .to_string()
             .yellow()
     );
-    Ok(())
+    let sol = |lamports: u64| lamports as f64 / 1_000_000_000.0;
+    Ok(vec![
+        ("Total SOL", sol(supply.total)),
+        ("Circulating SOL", sol(supply.circulating)),
+        ("Non-Circulating SOL", sol(supply.non_circulating)),
+    ])
 }
 
 pub fn get_inflation(client: &RpcClient) -> Result<()> {
@@ -86,7 +135,8 @@ pub fn get_inflation(client: &RpcClient) -> Result<()> {
     Ok(())
 }
 
-pub fn get_tps(client: &RpcClient) -> Result<()> {
+/// Show the current TPS; returns it for `--watch`.
+pub fn get_tps(client: &RpcClient) -> Result<Metrics> {
     let samples = client.get_recent_performance_samples(Some(5))?;
     println!(
         "\n{}",
@@ -101,10 +151,10 @@ pub fn get_tps(client: &RpcClient) -> Result<()> {
             "Current TPS",
             tps.to_string().green().bold()
         );
-    } else {
-        println!("No performance samples available.");
+        return Ok(vec![("TPS", (tps * 100.0).round() / 100.0)]);
     }
-    Ok(())
+    println!("No performance samples available.");
+    Ok(Vec::new())
 }
 
 pub fn get_epoch_schedule(client: &RpcClient) -> Result<()> {
@@ -118,3 +168,97 @@ pub fn get_epoch_schedule(client: &RpcClient) -> Result<()> {
     println!("{:<25} : {}", "Warmup", schedule.warmup);
     Ok(())
 }
+
+/// The first produced block at or after `slot`, with its block time.
+fn block_at_or_after(client: &RpcClient, slot: Slot) -> Result<Option<(Slot, UnixTimestamp)>> {
+    let Some(&block) = client.get_blocks_with_limit(slot, 1)?.first() else {
+        return Ok(None);
+    };
+    Ok(Some((block, client.get_block_time(block)?)))
+}
+
+/// Show when a slot was produced, or estimate when a future slot will be.
+pub fn slot_time(client: &RpcClient, slot: Slot) -> Result<()> {
+    let current = client.get_slot()?;
+    println!("\n{}", "--- Slot Time ---".bold().cyan());
+    println!("{:<20} : {}", "Slot", slot.to_string().yellow());
+
+    let now = chrono::Utc::now().timestamp();
+    if slot > current {
+        let secs = (slot - current) as f64 * average_slot_time(client)?;
+        let at = now + secs.round() as i64;
+        println!("{:<20} : {}", "Status", "Future (estimated)".yellow());
+        println!("{:<20} : {}", "Time", utils::format_utc(at).green());
+        println!("{:<20} : in {}", "Relative", utils::format_duration(secs));
+        return Ok(());
+    }
+
+    let (block, time) = block_at_or_after(client, slot)?
+        .ok_or_else(|| anyhow::anyhow!("No confirmed block at or after slot {}", slot))?;
+    if block == slot {
+        println!("{:<20} : {}", "Status", "Produced".green());
+    } else {
+        println!(
+            "{:<20} : {} (next block at slot {})",
+            "Status",
+            "Skipped".red(),
+            block
+        );
+    }
+    println!("{:<20} : {}", "Time", utils::format_utc(time).green());
+    println!(
+        "{:<20} : {} ago",
+        "Relative",
+        utils::format_duration((now - time) as f64)
+    );
+    Ok(())
+}
+
+/// Find the slot produced at a given time.
+///
+/// Past times are resolved by binary search over getBlockTime between the
+/// oldest available block and the current slot, returning the last block
+/// produced at or before the time. Future times are estimated.
+pub fn time_slot(client: &RpcClient, input: &str) -> Result<()> {
+    let target = utils::parse_date(input, false)?;
+    let current = client.get_slot()?;
+    let now = chrono::Utc::now().timestamp();
+
+    println!("\n{}", "--- Time to Slot ---".bold().cyan());
+    println!("{:<20} : {}", "Time", utils::format_utc(target).yellow());
+
+    if target >= now {
+        let slots = ((target - now) as f64 / average_slot_time(client)?) as u64;
+        println!("{:<20} : {}", "Status", "Future (estimated)".yellow());
+        println!("{:<20} : {}", "Slot", (current + slots).to_string().green());
+        return Ok(());
+    }
+
+    let first = client.get_first_available_block()?;
+    let (mut lo, lo_time) = block_at_or_after(client, first)?
+        .ok_or_else(|| anyhow::anyhow!("Node has no confirmed blocks"))?;
+    if lo_time > target {
+        anyhow::bail!(
+            "Time is before the oldest block available on this node (slot {} at {})",
+            lo,
+            utils::format_utc(lo_time)
+        );
+    }
+    let mut hi = current;
+    let mut probes = 0;
+    // Invariant: `lo` is a produced slot with block time <= target
+    while lo < hi {
+        let mid = lo + (hi - lo).div_ceil(2);
+        probes += 1;
+        match block_at_or_after(client, mid)? {
+            Some((block, time)) if block <= hi && time <= target => lo = block,
+            _ => hi = mid - 1,
+        }
+    }
+    let time = client.get_block_time(lo)?;
+
+    println!("{:<20} : {}", "Slot", lo.to_string().green());
+    println!("{:<20} : {}", "Block Time", utils::format_utc(time));
+    println!("{:<20} : {}", "Lookups", probes);
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use crate::commands::{stake, token};
+use crate::config::Config;
+use crate::labels;
+use crate::utils;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::stake::state::StakeStateV2;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::str::FromStr;
+
+/// Balances of a single wallet, in lamports.
+#[derive(Default)]
+struct WalletSummary {
+    liquid: u64,
+    stake_accounts: usize,
+    /// Total lamports held by the wallet's stake accounts.
+    stake_balance: u64,
+    active: u64,
+    activating: u64,
+    deactivating: u64,
+    token_accounts: usize,
+    token_rent: u64,
+}
+
+/// A mint's balance aggregated across wallets.
+#[derive(Default)]
+struct MintTotal {
+    amount: u128,
+    decimals: u8,
+    accounts: usize,
+    wallets: BTreeSet<String>,
+}
+
+fn sol(lamports: u64) -> String {
+    utils::format_sol(lamports as i128)
+}
+
+/// Aggregate liquid SOL, stake and token balances across several wallets.
+///
+/// When no address is given the watchlist from the config is used.
+pub fn show(client: &RpcClient, cfg: &Config, addresses: &[String]) -> Result<()> {
+    let addresses = if addresses.is_empty() {
+        cfg.watchlist.clone()
+    } else {
+        addresses.to_vec()
+    };
+    if addresses.is_empty() {
+        anyhow::bail!("No addresses given and the watchlist is empty (see `watchlist-add`)");
+    }
+    let wallets = addresses
+        .iter()
+        .map(|a| Pubkey::from_str(a).map_err(|_| anyhow::anyhow!("Invalid Address: {}", a)))
+        .collect::<Result<Vec<_>>>()?;
+
+    println!("Fetching balances for {} wallet(s)...", wallets.len());
+    let activation = stake::ActivationContext::fetch(client)?;
+
+    let mut summaries = Vec::new();
+    let mut mints: BTreeMap<String, MintTotal> = BTreeMap::new();
+    // A stake account can list two watched wallets as staker and withdrawer
+    let mut seen_stakes = HashSet::new();
+
+    for wallet in &wallets {
+        let mut summary = WalletSummary {
+            liquid: client.get_balance(wallet)?,
+            ..Default::default()
+        };
+
+        for (pubkey, account, state) in stake::find_by_authority(client, wallet)? {
+            if !seen_stakes.insert(pubkey) {
+                continue;
+            }
+            summary.stake_accounts += 1;
+            summary.stake_balance += account.lamports;
+            if let StakeStateV2::Stake(_, stake, _) = state {
+                let status = activation.status(&stake.delegation);
+                // Deactivating stake is still part of `effective`
+                summary.active += status.effective - status.deactivating;
+                summary.activating += status.activating;
+                summary.deactivating += status.deactivating;
+            }
+        }
+
+        for holding in token::fetch_holdings(client, wallet)? {
+            summary.token_accounts += 1;
+            summary.token_rent += holding.lamports;
+            let total = mints.entry(holding.mint).or_default();
+            total.amount += holding.amount;
+            total.decimals = holding.decimals;
+            total.accounts += 1;
+            total.wallets.insert(wallet.to_string());
+        }
+
+        summaries.push(summary);
+    }
+
+    println!(
+        "\n{}",
+        format!(
+            "--- Portfolio ({} wallets, epoch {}) ---",
+            wallets.len(),
+            activation.epoch
+        )
+        .bold()
+        .cyan()
+    );
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Wallet").style_spec("Fy"),
+        Cell::new("Liquid SOL").style_spec("Fg"),
+        Cell::new("Stake Accts").style_spec("Fc"),
+        Cell::new("Active").style_spec("Fg"),
+        Cell::new("Activating").style_spec("Fc"),
+        Cell::new("Deactivating").style_spec("Fm"),
+        Cell::new("Token Accts").style_spec("Fc"),
+        Cell::new("Token Rent").style_spec("Fm"),
+    ]));
+    let mut total = WalletSummary::default();
+    for (wallet, s) in wallets.iter().zip(&summaries) {
+        table.add_row(Row::new(vec![
+            Cell::new(&labels::display(&wallet.to_string())),
+            Cell::new(&sol(s.liquid)),
+            Cell::new(&s.stake_accounts.to_string()),
+            Cell::new(&sol(s.active)),
+            Cell::new(&sol(s.activating)),
+            Cell::new(&sol(s.deactivating)),
+            Cell::new(&s.token_accounts.to_string()),
+            Cell::new(&sol(s.token_rent)),
+        ]));
+        total.liquid += s.liquid;
+        total.stake_accounts += s.stake_accounts;
+        total.stake_balance += s.stake_balance;
+        total.active += s.active;
+        total.activating += s.activating;
+        total.deactivating += s.deactivating;
+        total.token_accounts += s.token_accounts;
+        total.token_rent += s.token_rent;
+    }
+    table.printstd();
+
+    println!("\n{}", "--- SOL Totals ---".bold().cyan());
+    println!("{:<25} : {} SOL", "Liquid", sol(total.liquid).green());
+    println!(
+        "{:<25} : {} SOL ({} accounts)",
+        "Stake Accounts Balance",
+        sol(total.stake_balance).green(),
+        total.stake_accounts
+    );
+    println!("{:<25} : {} SOL", "  Active", sol(total.active));
+    println!("{:<25} : {} SOL", "  Activating", sol(total.activating));
+    println!("{:<25} : {} SOL", "  Deactivating", sol(total.deactivating));
+    println!(
+        "{:<25} : {} SOL ({} accounts)",
+        "Token Account Rent",
+        sol(total.token_rent),
+        total.token_accounts
+    );
+    println!(
+        "{:<25} : {} SOL",
+        "Total",
+        sol(total.liquid + total.stake_balance + total.token_rent)
+            .green()
+            .bold()
+    );
+
+    println!(
+        "\n{}",
+        format!("--- Token Balances ({} mints) ---", mints.len())
+            .bold()
+            .cyan()
+    );
+    if mints.is_empty() {
+        println!("No token accounts found.");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Mint").style_spec("Fm"),
+        Cell::new("Amount").style_spec("Fg"),
+        Cell::new("Accounts").style_spec("Fc"),
+        Cell::new("Wallets").style_spec("Fy"),
+    ]));
+    for (mint, total) in &mints {
+        table.add_row(Row::new(vec![
+            Cell::new(&labels::display(mint)),
+            Cell::new(&utils::format_amount(total.amount as i128, total.decimals)),
+            Cell::new(&total.accounts.to_string()),
+            Cell::new(&total.wallets.len().to_string()),
+        ]));
+    }
+    table.printstd();
+
+    Ok(())
+}
+
+pub fn watchlist_add(cfg: &mut Config, address: String) -> Result<()> {
+    Pubkey::from_str(&address).map_err(|_| anyhow::anyhow!("Invalid Address"))?;
+    if cfg.watchlist.contains(&address) {
+        println!("{} is already on the watchlist.", address.yellow());
+        return Ok(());
+    }
+    cfg.watchlist.push(address.clone());
+    cfg.save()?;
+    println!("{} Added to watchlist: {}", "✔".green(), address.cyan());
+    Ok(())
+}
+
+pub fn watchlist_remove(cfg: &mut Config, address: String) -> Result<()> {
+    let before = cfg.watchlist.len();
+    cfg.watchlist.retain(|a| *a != address);
+    if cfg.watchlist.len() == before {
+        anyhow::bail!("{} is not on the watchlist", address);
+    }
+    cfg.save()?;
+    println!("{} Removed from watchlist: {}", "✔".green(), address.cyan());
+    Ok(())
+}
+
+pub fn watchlist_list(cfg: &Config) -> Result<()> {
+    println!(
+        "\n{}",
+        format!("--- Watchlist ({}) ---", cfg.watchlist.len())
+            .bold()
+            .cyan()
+    );
+    for (i, address) in cfg.watchlist.iter().enumerate() {
+        println!("[{:02}] {}", i, address.yellow());
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::labels;
 use anyhow::Result;
 use colored::*;
 use solana_client::rpc_client::RpcClient;
@@ -14,7 +15,14 @@ pub fn get_info(client: &RpcClient, prog_id: &str) -> Result<()> {
 
     println!("\n{}", "--- Program Details ---".bold().cyan());
     println!("{:<20} : {}", "Program ID", prog_id.yellow());
-    println!("{:<20} : {}", "Owner", account.owner);
+    if let Some(label) = labels::lookup(prog_id) {
+        println!("{:<20} : {}", "Label", label.cyan());
+    }
+    println!(
+        "{:<20} : {}",
+        "Owner",
+        labels::display(&account.owner.to_string())
+    );
     println!("{:<20} : {} bytes", "Data Size", account.data.len());
     println!(
         "{:<20} : {} SOL",
@@ -37,12 +45,20 @@ pub fn get_accounts(client: &RpcClient, prog_id: &str) -> Result<()> {
 
     println!(
         "\n{}",
-        format!("--- Owned Accounts ({}) ---", accounts.len())
-            .bold()
-            .cyan()
+        format!(
+            "--- Accounts Owned by {} ({}) ---",
+            labels::display(prog_id),
+            accounts.len()
+        )
+        .bold()
+        .cyan()
     );
     for (pk, acc) in accounts.iter().take(20) {
-        println!("{:<44} | {} lamports", pk.to_string(), acc.lamports);
+        println!(
+            "{:<44} | {} lamports",
+            labels::display(&pk.to_string()),
+            acc.lamports
+        );
     }
     if accounts.len() > 20 {
         println!("... and {} more.", accounts.len() - 20);
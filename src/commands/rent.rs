@@ -0,0 +1,125 @@
+use crate::labels;
+use crate::utils;
+use anyhow::Result;
+use colored::*;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::ReadableAccount;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::{ACCOUNT_STORAGE_OVERHEAD, Rent};
+use solana_sdk::sysvar;
+use std::str::FromStr;
+
+/// Read the cluster's rent parameters from the Rent sysvar.
+pub fn fetch_rent(client: &RpcClient) -> Result<Rent> {
+    let account = client.get_account(&sysvar::rent::id())?;
+    bincode::deserialize(account.data())
+        .map_err(|e| anyhow::anyhow!("Failed to decode Rent sysvar: {}", e))
+}
+
+/// Show the rent-exempt minimum for an account of `bytes` data bytes.
+pub fn calculate(client: &RpcClient, bytes: usize) -> Result<()> {
+    let rent = fetch_rent(client)?;
+    let minimum = client.get_minimum_balance_for_rent_exemption(bytes)?;
+
+    println!("\n{}", "--- Rent Exemption ---".bold().cyan());
+    println!("{:<25} : {} bytes", "Data Size", bytes);
+    println!(
+        "{:<25} : {} bytes",
+        "Stored Size",
+        bytes as u64 + ACCOUNT_STORAGE_OVERHEAD
+    );
+    println!(
+        "{:<25} : {}",
+        "Lamports per Byte-Year", rent.lamports_per_byte_year
+    );
+    println!(
+        "{:<25} : {} years",
+        "Exemption Threshold", rent.exemption_threshold
+    );
+    println!(
+        "{:<25} : {} SOL ({} lamports)",
+        "Rent-Exempt Minimum",
+        utils::format_sol(minimum as i128).green().bold(),
+        minimum
+    );
+    // The RPC and the sysvar should agree; flag nodes that do not
+    let local = rent.minimum_balance(bytes);
+    if local != minimum {
+        println!(
+            "{}",
+            format!(
+                "Warning: the Rent sysvar gives {} lamports, the RPC node {}",
+                local, minimum
+            )
+            .yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Check whether an account is rent exempt and what closing it would return.
+pub fn audit(client: &RpcClient, address_str: &str) -> Result<()> {
+    let pubkey = Pubkey::from_str(address_str)
+        .map_err(|_| anyhow::anyhow!("Invalid Solana address format"))?;
+    let account = client.get_account(&pubkey)?;
+    let rent = fetch_rent(client)?;
+    let minimum = rent.minimum_balance(account.data.len());
+    let excess = account.lamports as i128 - minimum as i128;
+
+    println!("\n{}", "--- Rent Audit ---".bold().cyan());
+    println!("{:<25} : {}", "Address", address_str.yellow());
+    println!(
+        "{:<25} : {}",
+        "Owner Program",
+        labels::display(&account.owner.to_string()).magenta()
+    );
+    println!("{:<25} : {} bytes", "Data Size", account.data.len());
+    println!(
+        "{:<25} : {} SOL",
+        "Balance",
+        utils::format_sol(account.lamports as i128)
+    );
+    println!(
+        "{:<25} : {} SOL",
+        "Rent-Exempt Minimum",
+        utils::format_sol(minimum as i128)
+    );
+    println!(
+        "{:<25} : {}",
+        "Rent Exempt",
+        if excess >= 0 {
+            "Yes".green()
+        } else {
+            "No".red()
+        }
+    );
+    if excess >= 0 {
+        println!(
+            "{:<25} : {} SOL",
+            "Above Minimum",
+            utils::format_sol(excess)
+        );
+    } else {
+        println!(
+            "{:<25} : {} SOL",
+            "Shortfall",
+            utils::format_sol(-excess).red()
+        );
+    }
+
+    // Closing an account returns its entire balance to the destination
+    if account.executable {
+        println!(
+            "{:<25} : {}",
+            "Reclaimable on Close",
+            "N/A (executable account)".yellow()
+        );
+    } else {
+        println!(
+            "{:<25} : {} SOL",
+            "Reclaimable on Close",
+            utils::format_sol(account.lamports as i128).green()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use crate::commands::network;
+use crate::labels;
+use crate::utils;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_response::RpcInflationReward;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0;
+
+/// One epoch's reward as written to the CSV file.
+#[derive(Serialize)]
+struct RewardRow {
+    address: String,
+    epoch: u64,
+    effective_slot: u64,
+    timestamp: String,
+    amount: String,
+    post_balance: String,
+    commission: Option<u8>,
+    apy_percent: String,
+}
+
+/// Annualized yield of a reward, compounded once per epoch.
+fn apy(reward: &RpcInflationReward, epochs_per_year: f64) -> Option<f64> {
+    let pre_balance = reward.post_balance.checked_sub(reward.amount)?;
+    if pre_balance == 0 {
+        return None;
+    }
+    let rate = reward.amount as f64 / pre_balance as f64;
+    Some(((1.0 + rate).powf(epochs_per_year) - 1.0) * 100.0)
+}
+
+/// Show inflation rewards of stake or vote accounts over the last `epochs`
+/// completed epochs, optionally writing them to a CSV file.
+///
+/// APY assumes every epoch lasts as long as the current one at the recent
+/// average slot time.
+pub fn show(
+    client: &RpcClient,
+    addresses: &[String],
+    epochs: u64,
+    out: Option<&Path>,
+) -> Result<()> {
+    let pubkeys = addresses
+        .iter()
+        .map(|a| Pubkey::from_str(a).map_err(|_| anyhow::anyhow!("Invalid Address: {}", a)))
+        .collect::<Result<Vec<_>>>()?;
+    let epoch_info = client.get_epoch_info()?;
+    let epoch_secs = epoch_info.slots_in_epoch as f64 * network::average_slot_time(client)?;
+    let epochs_per_year = SECONDS_PER_YEAR / epoch_secs;
+
+    // rewards[address index] = rewards newest epoch first
+    let mut rewards: Vec<Vec<RpcInflationReward>> = vec![Vec::new(); pubkeys.len()];
+    // Payout time of each epoch's rewards, from its effective slot
+    let mut paid_at: HashMap<u64, i64> = HashMap::new();
+    let last = epoch_info.epoch.saturating_sub(1);
+    let first = epoch_info.epoch.saturating_sub(epochs);
+    for epoch in (first..=last).rev() {
+        eprint!("\rFetching rewards for epoch {}...", epoch);
+        let results = match client.get_inflation_reward(&pubkeys, Some(epoch)) {
+            Ok(results) => results,
+            Err(e) => {
+                // Nodes only keep rewards for the blocks they still store
+                eprintln!(
+                    "\n{}",
+                    format!("Stopping at epoch {}: {}", epoch, e).yellow()
+                );
+                break;
+            }
+        };
+        for (i, reward) in results.into_iter().enumerate() {
+            if let Some(reward) = reward {
+                if !paid_at.contains_key(&epoch)
+                    && let Ok(time) = client.get_block_time(reward.effective_slot)
+                {
+                    paid_at.insert(epoch, time);
+                }
+                rewards[i].push(reward);
+            }
+        }
+    }
+    eprintln!();
+
+    let mut rows = Vec::new();
+    let mut grand_total = 0u64;
+    for (address, rewards) in addresses.iter().zip(&rewards) {
+        println!(
+            "\n{}",
+            format!("--- Inflation Rewards: {} ---", labels::display(address))
+                .bold()
+                .cyan()
+        );
+        if rewards.is_empty() {
+            println!("No rewards found in the last {} epochs.", epochs);
+            continue;
+        }
+
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("Epoch").style_spec("Fy"),
+            Cell::new("Effective Slot").style_spec("Fm"),
+            Cell::new("Paid At").style_spec("Fc"),
+            Cell::new("Reward (SOL)").style_spec("Fg"),
+            Cell::new("Post Balance (SOL)").style_spec("Fc"),
+            Cell::new("Commission").style_spec("Fm"),
+            Cell::new("APY").style_spec("Fg"),
+        ]));
+        let mut total = 0u64;
+        let mut apys = Vec::new();
+        for reward in rewards {
+            let apy = apy(reward, epochs_per_year);
+            apys.extend(apy);
+            total += reward.amount;
+            let apy = apy.map(|a| format!("{:.2}", a)).unwrap_or_default();
+            let paid = paid_at.get(&reward.epoch);
+            table.add_row(Row::new(vec![
+                Cell::new(&reward.epoch.to_string()),
+                Cell::new(&reward.effective_slot.to_string()),
+                Cell::new(&paid.map(|t| utils::format_utc(*t)).unwrap_or_default()),
+                Cell::new(&utils::format_sol(reward.amount as i128)),
+                Cell::new(&utils::format_sol(reward.post_balance as i128)),
+                Cell::new(
+                    &reward
+                        .commission
+                        .map(|c| format!("{}%", c))
+                        .unwrap_or_default(),
+                ),
+                Cell::new(&if apy.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}%", apy)
+                }),
+            ]));
+            rows.push(RewardRow {
+                address: address.clone(),
+                epoch: reward.epoch,
+                effective_slot: reward.effective_slot,
+                timestamp: paid
+                    .and_then(|t| chrono::DateTime::from_timestamp(*t, 0))
+                    .map(|dt| dt.to_rfc3339())
+                    .unwrap_or_default(),
+                amount: utils::format_sol(reward.amount as i128),
+                post_balance: utils::format_sol(reward.post_balance as i128),
+                commission: reward.commission,
+                apy_percent: apy,
+            });
+        }
+        table.printstd();
+        grand_total += total;
+
+        println!(
+            "{:<25} : {} SOL over {} epochs",
+            "Total Rewards",
+            utils::format_sol(total as i128).green().bold(),
+            rewards.len()
+        );
+        if !apys.is_empty() {
+            println!(
+                "{:<25} : {:.2}%",
+                "Average APY",
+                apys.iter().sum::<f64>() / apys.len() as f64
+            );
+        }
+    }
+
+    if addresses.len() > 1 {
+        println!(
+            "\n{:<25} : {} SOL",
+            "Total (All Accounts)",
+            utils::format_sol(grand_total as i128).green().bold()
+        );
+    }
+
+    if let Some(path) = out {
+        let mut writer = csv::Writer::from_path(path)?;
+        for row in &rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        println!(
+            "{:<25} : {} rows to {}",
+            "Saved",
+            rows.len(),
+            path.display()
+        );
+    }
+    Ok(())
+}
@@ -1,7 +1,18 @@
 use anyhow::Result;
 use colored::*;
+use solana_account_decoder::UiAccountEncoding;
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Epoch;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::stake::{
+    self,
+    state::{Delegation, StakeActivationStatus, StakeStateV2},
+};
+use solana_sdk::stake_history::StakeHistory;
+use solana_sdk::{feature, feature_set, sysvar};
 use std::str::FromStr;
 
 pub fn get_stake_account(client: &RpcClient, address: &str) -> Result<()> {
@@ -47,3 +58,87 @@ pub fn get_stake_account(client: &RpcClient, address: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Byte offset of the staker authority in a stake account.
+const STAKER_OFFSET: usize = 12;
+/// Byte offset of the withdrawer authority in a stake account.
+const WITHDRAWER_OFFSET: usize = 44;
+
+/// Epoch state needed to compute how much of a delegation is active.
+pub struct ActivationContext {
+    pub epoch: Epoch,
+    history: StakeHistory,
+    new_rate_activation_epoch: Option<Epoch>,
+}
+
+impl ActivationContext {
+    /// Fetch the current epoch, the StakeHistory sysvar and the epoch in which
+    /// the reduced warmup/cooldown rate took effect.
+    pub fn fetch(client: &RpcClient) -> Result<Self> {
+        let epoch = client.get_epoch_info()?.epoch;
+        let history_account = client.get_account(&sysvar::stake_history::id())?;
+        let history: StakeHistory = bincode::deserialize(&history_account.data)?;
+
+        let feature_id = feature_set::reduce_stake_warmup_cooldown::id();
+        let activated_slot = client
+            .get_multiple_accounts(&[feature_id])?
+            .into_iter()
+            .flatten()
+            .find_map(|account| feature::from_account(&account))
+            .and_then(|f| f.activated_at);
+        let new_rate_activation_epoch = match activated_slot {
+            Some(slot) => Some(client.get_epoch_schedule()?.get_epoch(slot)),
+            None => None,
+        };
+
+        Ok(Self {
+            epoch,
+            history,
+            new_rate_activation_epoch,
+        })
+    }
+
+    /// Effective, activating and deactivating lamports of a delegation.
+    ///
+    /// `effective` includes the stake that is deactivating; the stake that
+    /// is fully active is `effective - deactivating`.
+    pub fn status(&self, delegation: &Delegation) -> StakeActivationStatus {
+        delegation.stake_activating_and_deactivating(
+            self.epoch,
+            &self.history,
+            self.new_rate_activation_epoch,
+        )
+    }
+}
+
+/// Find every stake account whose staker or withdrawer is `authority`.
+pub fn find_by_authority(
+    client: &RpcClient,
+    authority: &Pubkey,
+) -> Result<Vec<(Pubkey, Account, StakeStateV2)>> {
+    let mut found: Vec<(Pubkey, Account, StakeStateV2)> = Vec::new();
+    for offset in [STAKER_OFFSET, WITHDRAWER_OFFSET] {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                offset,
+                authority.as_ref(),
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            with_context: None,
+        };
+        for (pubkey, account) in
+            client.get_program_accounts_with_config(&stake::program::id(), config)?
+        {
+            if found.iter().any(|(pk, ..)| *pk == pubkey) {
+                continue;
+            }
+            if let Ok(state) = bincode::deserialize::<StakeStateV2>(&account.data) {
+                found.push((pubkey, account, state));
+            }
+        }
+    }
+    Ok(found)
+}
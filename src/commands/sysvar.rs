@@ -0,0 +1,105 @@
+use crate::decode;
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::sysvar;
+
+/// Sysvars that can be read from their accounts.
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum SysvarName {
+    Clock,
+    Rent,
+    EpochSchedule,
+    SlotHashes,
+    StakeHistory,
+    RecentBlockhashes,
+    EpochRewards,
+    LastRestartSlot,
+    SlotHistory,
+}
+
+impl SysvarName {
+    const ALL: [SysvarName; 9] = [
+        SysvarName::Clock,
+        SysvarName::Rent,
+        SysvarName::EpochSchedule,
+        SysvarName::SlotHashes,
+        SysvarName::StakeHistory,
+        SysvarName::RecentBlockhashes,
+        SysvarName::EpochRewards,
+        SysvarName::LastRestartSlot,
+        SysvarName::SlotHistory,
+    ];
+
+    #[allow(deprecated)] // RecentBlockhashes is deprecated but still readable
+    fn id(self) -> Pubkey {
+        match self {
+            SysvarName::Clock => sysvar::clock::id(),
+            SysvarName::Rent => sysvar::rent::id(),
+            SysvarName::EpochSchedule => sysvar::epoch_schedule::id(),
+            SysvarName::SlotHashes => sysvar::slot_hashes::id(),
+            SysvarName::StakeHistory => sysvar::stake_history::id(),
+            SysvarName::RecentBlockhashes => sysvar::recent_blockhashes::id(),
+            SysvarName::EpochRewards => sysvar::epoch_rewards::id(),
+            SysvarName::LastRestartSlot => sysvar::last_restart_slot::id(),
+            SysvarName::SlotHistory => sysvar::slot_history::id(),
+        }
+    }
+
+    fn cli_name(self) -> String {
+        clap::ValueEnum::to_possible_value(&self)
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Read a sysvar account and decode it.
+///
+/// `limit` caps the entries shown for list-valued sysvars (0 shows all).
+pub fn show(client: &RpcClient, name: SysvarName, limit: usize) -> Result<()> {
+    let address = name.id();
+    let Some(account) = client
+        .get_account_with_commitment(&address, client.commitment())?
+        .value
+    else {
+        anyhow::bail!("Sysvar {} does not exist on this cluster", name.cli_name());
+    };
+    let limit = if limit == 0 { usize::MAX } else { limit };
+    let fields = decode::sysvar(&address, &account.data, limit)?;
+
+    println!("\n{}", "--- Sysvar ---".bold().cyan());
+    println!("{:<25} : {}", "Address", address.to_string().yellow());
+    println!("{:<25} : {} bytes", "Data Size", account.data.len());
+    decode::print_fields(&fields);
+    Ok(())
+}
+
+/// List the readable sysvars and whether they exist on the cluster.
+pub fn list(client: &RpcClient) -> Result<()> {
+    let ids: Vec<Pubkey> = SysvarName::ALL.iter().map(|s| s.id()).collect();
+    let accounts = client.get_multiple_accounts(&ids)?;
+
+    println!("\n{}", "--- Sysvars ---".bold().cyan());
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Name").style_spec("Fc"),
+        Cell::new("Address").style_spec("Fy"),
+        Cell::new("Size").style_spec("Fm"),
+    ]));
+    for ((name, id), account) in SysvarName::ALL.iter().zip(&ids).zip(&accounts) {
+        let size = match account {
+            Some(account) => format!("{} bytes", account.data.len()),
+            None => "Not present".to_string(),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&name.cli_name()),
+            Cell::new(&id.to_string()),
+            Cell::new(&size),
+        ]));
+    }
+    table.printstd();
+    println!("Run `sysvar <NAME>` to decode one.");
+    Ok(())
+}
@@ -1,96 +1,117 @@
+use crate::decode;
+use crate::labels;
 use anyhow::Result;
 use colored::*;
-use prettytable::{Cell, Row, Table};
+use solana_account_decoder::UiAccountData;
 use solana_client::rpc_client::RpcClient;
 use solana_client::rpc_request::TokenAccountsFilter;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
-/// List SPL Token accounts for a wallet.
-pub fn get_token_accounts(client: &RpcClient, owner_str: &str) -> Result<()> {
-    let owner =
-        Pubkey::from_str(owner_str).map_err(|_| anyhow::anyhow!("Invalid Owner Address"))?;
-
-    println!("Fetching token accounts for {}...", owner_str);
-
-    let accounts = client.get_token_accounts_by_owner(
-        &owner,
-        TokenAccountsFilter::ProgramId(
-            solana_sdk::pubkey::Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
-                .unwrap(),
-        ),
-    )?;
-
-    println!(
-        "\n{}",
-        format!("--- Token Accounts ({}) ---", accounts.len())
-            .bold()
-            .cyan()
-    );
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// A single token account balance held by an owner.
+pub struct TokenHolding {
+    pub mint: String,
+    /// Raw amount in base units.
+    pub amount: u128,
+    pub decimals: u8,
+    /// Lamports locked in the token account for rent exemption.
+    pub lamports: u64,
+}
 
-    if accounts.is_empty() {
-        println!("No token accounts found.");
-        return Ok(());
+/// Fetch every SPL Token and Token-2022 account owned by `owner`.
+pub fn fetch_holdings(client: &RpcClient, owner: &Pubkey) -> Result<Vec<TokenHolding>> {
+    let mut holdings = Vec::new();
+    for program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+        let accounts = client.get_token_accounts_by_owner(
+            owner,
+            TokenAccountsFilter::ProgramId(Pubkey::from_str(program).unwrap()),
+        )?;
+        for keyed in accounts {
+            // The RPC returns jsonParsed data for token accounts
+            let UiAccountData::Json(parsed) = &keyed.account.data else {
+                continue;
+            };
+            let info = &parsed.parsed["info"];
+            let amount = &info["tokenAmount"];
+            holdings.push(TokenHolding {
+                mint: info["mint"].as_str().unwrap_or_default().to_string(),
+                amount: amount["amount"]
+                    .as_str()
+                    .and_then(|a| a.parse().ok())
+                    .unwrap_or(0),
+                decimals: amount["decimals"].as_u64().unwrap_or(0) as u8,
+                lamports: keyed.account.lamports,
+            });
+        }
     }
+    Ok(holdings)
+}
 
-    let mut table = Table::new();
-    table.add_row(Row::new(vec![
-        Cell::new("Mint").style_spec("Fm"),
-        Cell::new("Amount").style_spec("Fg"),
-        Cell::new("Decimals").style_spec("Fc"),
-    ]));
-
-    for act in &accounts {
-        // Parse account data to get mint and amount
-        // This is a simplified approach. In a real deep tool we would parse the bytes using spl_token crate directly
-        // or ask RPC for parsed JSON.
-        // For efficiency in this demo, let's just attempt to parse or show raw if we stick to the basics.
-        // Re-fetching as Parsed is better.
-
-        // Let's rely on account data parsing which is safer with `get_token_accounts_by_owner` json parsed config
-        // But the robust client method returns keyed accounts.
-        // We will do a generic display here or fetch generic parsed.
-
-        // Simulating the display since we need `spl-token` crate for byte drilling or use JSON parsed RPC.
-        // We'll trust the user wants speed, so we'll just show the count for now or implement parsed fetch if desired.
-        // Let's actually use the `get_parsed_token_accounts_by_owner` for better output.
+/// Get details about a specific Token Mint.
+pub fn get_mint_info(client: &RpcClient, mint_str: &str) -> Result<()> {
+    let mint = Pubkey::from_str(mint_str).map_err(|_| anyhow::anyhow!("Invalid Mint Address"))?;
+    let account = client.get_account(&mint)?;
+    if !decode::is_token_program(&account.owner) {
+        anyhow::bail!(
+            "Account is not owned by a token program (owner: {})",
+            labels::display(&account.owner.to_string())
+        );
     }
+    let fields = decode::mint(&account.data)?;
 
-    // Switch to parsed fetch for human readable data
-    // Switch to parsed fetch for human readable data
-    // let parsed_accounts = client.get_token_accounts_by_owner(
-    //    &owner,
-    //    TokenAccountsFilter::ProgramId(solana_sdk::pubkey::Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap()),
-    // )?;
-
-    // NOTE: The standard solana-client returning `UiTokenAmount` is wrapped in Account data.
-    // Since we don't have the full `spl-token` struct locally easily without adding crate,
-    // we'll display the fact they exist.
-
-    // To make this really "Pro", let's use the CLI method:
-    // Actually, `solana-account-decoder` comes with sdk.
-    // Let's implement a known Mint info fetch.
-
+    println!("\n{}", "--- Token Mint Details ---".bold().cyan());
+    println!("{:<25} : {}", "Address", mint_str.yellow());
+    if let Some(label) = labels::lookup(mint_str) {
+        println!("{:<25} : {}", "Label", label.cyan());
+    }
     println!(
-        "Found {} token accounts (Detail view requires fetching parse info).",
-        accounts.len()
+        "{:<25} : {}",
+        "Owner Program",
+        labels::display(&account.owner.to_string())
     );
+    println!("{:<25} : {} bytes", "Data Size", account.data.len());
+    decode::print_fields(&fields);
 
     Ok(())
 }
 
-/// Get details about a specific Token Mint.
-pub fn get_mint_info(client: &RpcClient, mint_str: &str) -> Result<()> {
-    let mint = Pubkey::from_str(mint_str).map_err(|_| anyhow::anyhow!("Invalid Mint Address"))?;
-    let account = client.get_account(&mint)?;
+/// Get details of a single token account (Mint, Owner, Amount, Delegate).
+pub fn get_token_account(client: &RpcClient, address: &str) -> Result<()> {
+    let pubkey =
+        Pubkey::from_str(address).map_err(|_| anyhow::anyhow!("Invalid Token Account Address"))?;
+    let account = client.get_account(&pubkey)?;
+    if !decode::is_token_program(&account.owner) {
+        anyhow::bail!(
+            "Account is not owned by a token program (owner: {})",
+            labels::display(&account.owner.to_string())
+        );
+    }
+    let fields = decode::token_account(&account.data)?;
 
-    println!("\n{}", "--- Token Mint Details ---".bold().cyan());
-    println!("{:<20} : {}", "Address", mint_str.yellow());
-    println!("{:<20} : {} bytes", "Data Size", account.data.len());
-    println!("{:<20} : {}", "Owner Program", account.owner);
-    // Deep parsing requires `spl_token::state::Mint` unpacking
-    // Without adding `spl-token` cargo, we can't safely unpack.
-    // We will show raw info.
+    // The UI amount needs the mint's decimals
+    let balance = client.get_token_account_balance(&pubkey)?;
+
+    println!("\n{}", "--- Token Account Details ---".bold().cyan());
+    println!("{:<25} : {}", "Address", address.yellow());
+    println!(
+        "{:<25} : {}",
+        "Owner Program",
+        labels::display(&account.owner.to_string())
+    );
+    println!(
+        "{:<25} : {}",
+        "Balance",
+        balance.ui_amount_string.green().bold()
+    );
+    decode::print_fields(&fields);
+    println!(
+        "{:<25} : {:.9} SOL",
+        "Rent Lamports",
+        account.lamports as f64 / 1_000_000_000.0
+    );
 
     Ok(())
 }
@@ -108,14 +129,14 @@ pub fn get_holders(client: &RpcClient, mint_address: &str) -> Result<()> {
             .bold()
             .cyan()
     );
-    println!("{:<45} | {:<20} | {}", "Address", "Amount", "Decimals");
+    println!("{:<45} | {:<20} | Decimals", "Address", "Amount");
     println!("{}", "-".repeat(80));
 
     for (i, acc) in accounts.iter().enumerate() {
         println!(
             "[{:02}] {:<45} | {:<20} | {}",
             i + 1,
-            acc.address.yellow(),
+            labels::display(&acc.address).yellow(),
             acc.amount.ui_amount_string,
             acc.amount.decimals
         );
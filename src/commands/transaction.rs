@@ -1,40 +1,529 @@
+use crate::anchor;
+use crate::commands::logs;
+use crate::decode;
+use crate::labels;
 use anyhow::Result;
+use base64::prelude::*;
 use chrono;
 use colored::*;
+use prettytable::{Cell, Row, Table};
+use serde_json::Value;
 use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::CompiledInstruction;
+use solana_sdk::message::{AccountKeys, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::{TransactionVersion, VersionedTransaction};
+use solana_transaction_status::parse_instruction;
 use solana_transaction_status::{
-    EncodedTransaction, UiMessage, UiTransactionEncoding, option_serializer::OptionSerializer,
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiCompiledInstruction,
+    UiLoadedAddresses, UiMessage, UiRawMessage, UiTransactionEncoding,
+    option_serializer::OptionSerializer,
 };
+use std::io::Read;
+use std::path::Path;
 use std::str::FromStr;
 
+/// Bytes of undecoded instruction data shown before truncating.
+const MAX_DATA_PREVIEW: usize = 64;
+
+/// Where a transaction account key was loaded from.
+pub enum KeySource {
+    /// Listed directly in the message.
+    Static,
+    /// Loaded from an address lookup table (v0 transactions).
+    Lookup { table: String, index: u8 },
+}
+
+/// An account key of a transaction with its access flags and origin.
+pub struct ResolvedKey {
+    pub pubkey: String,
+    pub signer: bool,
+    pub writable: bool,
+    pub source: KeySource,
+}
+
+/// Build the full account list of a message.
+///
+/// Static keys come first, followed by the writable and then the readonly
+/// addresses loaded from lookup tables, matching the runtime's ordering.
+/// `loaded` is the `loadedAddresses` field of the transaction meta; lookups
+/// it does not cover are reported as `<unresolved>`.
+pub fn resolve_account_keys(
+    msg: &UiRawMessage,
+    loaded: Option<&UiLoadedAddresses>,
+) -> Vec<ResolvedKey> {
+    let header = &msg.header;
+    let num_signers = header.num_required_signatures as usize;
+    let num_static = msg.account_keys.len();
+
+    let mut keys: Vec<ResolvedKey> = msg
+        .account_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let writable = if i < num_signers {
+                i < num_signers - header.num_readonly_signed_accounts as usize
+            } else {
+                i < num_static - header.num_readonly_unsigned_accounts as usize
+            };
+            ResolvedKey {
+                pubkey: key.clone(),
+                signer: i < num_signers,
+                writable,
+                source: KeySource::Static,
+            }
+        })
+        .collect();
+
+    let lookups = match &msg.address_table_lookups {
+        Some(lookups) => lookups,
+        None => return keys,
+    };
+
+    let unresolved = || "<unresolved>".to_string();
+    let mut writable = loaded.map(|l| l.writable.iter());
+    for lookup in lookups {
+        for &index in &lookup.writable_indexes {
+            let pubkey = writable
+                .as_mut()
+                .and_then(|it| it.next().cloned())
+                .unwrap_or_else(unresolved);
+            keys.push(ResolvedKey {
+                pubkey,
+                signer: false,
+                writable: true,
+                source: KeySource::Lookup {
+                    table: lookup.account_key.clone(),
+                    index,
+                },
+            });
+        }
+    }
+
+    let mut readonly = loaded.map(|l| l.readonly.iter());
+    for lookup in lookups {
+        for &index in &lookup.readonly_indexes {
+            let pubkey = readonly
+                .as_mut()
+                .and_then(|it| it.next().cloned())
+                .unwrap_or_else(unresolved);
+            keys.push(ResolvedKey {
+                pubkey,
+                signer: false,
+                writable: false,
+                source: KeySource::Lookup {
+                    table: lookup.account_key.clone(),
+                    index,
+                },
+            });
+        }
+    }
+
+    keys
+}
+
+/// Fetch a finalized transaction with JSON encoding, accepting v0 messages.
+pub fn fetch(
+    client: &RpcClient,
+    signature: &Signature,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    // JSON encoding keeps instructions/accounts easy to parse
+    Ok(client.get_transaction_with_config(
+        signature,
+        solana_client::rpc_config::RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(solana_sdk::commitment_config::CommitmentConfig::finalized()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?)
+}
+
+/// The raw message of a JSON encoded transaction, if present.
+pub fn raw_message(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<&UiRawMessage> {
+    match &tx.transaction.transaction {
+        EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
+            UiMessage::Raw(msg) => Some(msg),
+            UiMessage::Parsed(_) => None,
+        },
+        _ => None,
+    }
+}
+
+/// Full account list of a fetched transaction, using the meta's loaded addresses.
+pub fn account_keys(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<ResolvedKey> {
+    let loaded = tx
+        .transaction
+        .meta
+        .as_ref()
+        .and_then(|m| match &m.loaded_addresses {
+            OptionSerializer::Some(loaded) => Some(loaded),
+            _ => None,
+        });
+    raw_message(tx)
+        .map(|msg| resolve_account_keys(msg, loaded))
+        .unwrap_or_default()
+}
+
+/// Print the account list of a transaction as a table of flags and origins.
+pub fn print_account_keys(keys: &[ResolvedKey]) {
+    if keys.is_empty() {
+        return;
+    }
+    println!(
+        "\n{}",
+        format!("--- Account Keys ({}) ---", keys.len())
+            .bold()
+            .cyan()
+    );
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Index").style_spec("Fm"),
+        Cell::new("Address").style_spec("Fy"),
+        Cell::new("Flags").style_spec("Fc"),
+        Cell::new("Source").style_spec("Fg"),
+    ]));
+    for (i, key) in keys.iter().enumerate() {
+        let mut flags = Vec::new();
+        if key.signer {
+            flags.push("signer");
+        }
+        if key.writable {
+            flags.push("writable");
+        }
+        let source = match &key.source {
+            KeySource::Static => "static".to_string(),
+            KeySource::Lookup { table, index } => {
+                format!("{} [{}]", labels::display(table), index)
+            }
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&i.to_string()),
+            Cell::new(&labels::display(&key.pubkey)),
+            Cell::new(&flags.join(", ")),
+            Cell::new(&source),
+        ]));
+    }
+    table.printstd();
+}
+
+/// Print the top-level instructions of a message.
+///
+/// Instructions of the System, Stake, Vote, Token, ATA, Memo, loader and
+/// lookup table programs are decoded into their fields; others show their
+/// accounts and raw data.
+pub fn print_instructions(msg: &UiRawMessage, keys: &[ResolvedKey]) {
+    println!(
+        "\n{}",
+        format!("--- Instructions ({}) ---", msg.instructions.len())
+            .bold()
+            .cyan()
+    );
+    for (i, ix) in msg.instructions.iter().enumerate() {
+        let program = keys
+            .get(ix.program_id_index as usize)
+            .map(|k| k.pubkey.as_str())
+            .unwrap_or("<unknown>");
+        let data = bs58::decode(&ix.data).into_vec().unwrap_or_default();
+        match parse_instruction(ix, &data, keys) {
+            Some(parsed) => {
+                let (kind, fields) = match &parsed {
+                    Value::String(memo) => {
+                        ("memo".to_string(), vec![("memo".into(), memo.clone())])
+                    }
+                    other => (
+                        other["type"].as_str().unwrap_or("unknown").to_string(),
+                        decode::json_fields(&other["info"]),
+                    ),
+                };
+                println!(
+                    "[{:02}] {} › {}",
+                    i,
+                    labels::display(program).purple(),
+                    kind.green()
+                );
+                for (name, value) in fields {
+                    println!("     {:<20} : {}", name, value);
+                }
+            }
+            None => {
+                println!("[{:02}] {}", i, labels::display(program).purple());
+                for (n, &index) in ix.accounts.iter().enumerate() {
+                    let account = keys
+                        .get(index as usize)
+                        .map(|k| labels::display(&k.pubkey))
+                        .unwrap_or_else(|| format!("<index {}>", index));
+                    println!("     {:<20} : {}", format!("account[{}]", n), account);
+                }
+                let shown = &data[..data.len().min(MAX_DATA_PREVIEW)];
+                let mut hex = anchor::hex(shown);
+                if data.len() > shown.len() {
+                    hex.push_str(&format!("… ({} bytes)", data.len()));
+                }
+                println!("     {:<20} : 0x{}", "data", hex);
+            }
+        }
+    }
+}
+
+/// Decode an instruction with the RPC's jsonParsed parsers.
+///
+/// Returns `None` when the program is not parsable or an account it
+/// references could not be resolved.
+fn parse_instruction(
+    ix: &UiCompiledInstruction,
+    data: &[u8],
+    keys: &[ResolvedKey],
+) -> Option<Value> {
+    let pubkeys = keys
+        .iter()
+        .map(|k| Pubkey::from_str(&k.pubkey).ok())
+        .collect::<Vec<_>>();
+    let resolved = |i: &u8| pubkeys.get(*i as usize).is_some_and(Option::is_some);
+    if !resolved(&ix.program_id_index) || !ix.accounts.iter().all(resolved) {
+        return None;
+    }
+    // Unresolved lookups are only placeholders here; every referenced one was checked
+    let pubkeys: Vec<Pubkey> = pubkeys.into_iter().map(Option::unwrap_or_default).collect();
+    let compiled = CompiledInstruction {
+        program_id_index: ix.program_id_index,
+        accounts: ix.accounts.clone(),
+        data: data.to_vec(),
+    };
+    parse_instruction::parse(
+        &pubkeys[ix.program_id_index as usize],
+        &compiled,
+        &AccountKeys::new(&pubkeys, None),
+        None,
+    )
+    .ok()
+    .map(|parsed| parsed.parsed)
+}
+
+/// Convert a deserialized message into the RPC's raw JSON representation.
+fn ui_raw_message(message: &VersionedMessage) -> UiRawMessage {
+    UiRawMessage {
+        header: *message.header(),
+        account_keys: message
+            .static_account_keys()
+            .iter()
+            .map(|k| k.to_string())
+            .collect(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        instructions: message
+            .instructions()
+            .iter()
+            .map(|ix| UiCompiledInstruction {
+                program_id_index: ix.program_id_index,
+                accounts: ix.accounts.clone(),
+                data: bs58::encode(&ix.data).into_string(),
+                stack_height: None,
+            })
+            .collect(),
+        address_table_lookups: message
+            .address_table_lookups()
+            .map(|lookups| lookups.iter().map(Into::into).collect()),
+    }
+}
+
+/// Encoding of a serialized transaction passed to `tx-decode`.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TxEncoding {
+    Base64,
+    Base58,
+}
+
+/// Read the serialized transaction from an argument, a file or stdin.
+fn read_input(input: Option<&str>, file: Option<&Path>) -> Result<String> {
+    let text = match (input, file) {
+        (_, Some(path)) => std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?,
+        (Some(tx), None) if tx != "-" => tx.to_string(),
+        _ => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            buf
+        }
+    };
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        anyhow::bail!("No transaction provided");
+    }
+    Ok(text)
+}
+
+/// Deserialize and sanitize a transaction in wire format.
+fn deserialize_transaction(bytes: &[u8]) -> Result<VersionedTransaction> {
+    let tx: VersionedTransaction = bincode::deserialize(bytes)
+        .map_err(|e| anyhow::anyhow!("failed to deserialize transaction: {}", e))?;
+    let size = bincode::serialized_size(&tx)?;
+    if size != bytes.len() as u64 {
+        anyhow::bail!("{} unexpected trailing bytes", bytes.len() as u64 - size);
+    }
+    tx.sanitize()
+        .map_err(|e| anyhow::anyhow!("malformed transaction: {}", e))?;
+    Ok(tx)
+}
+
+/// Decode base64 or base58 text into a transaction and its wire bytes.
+///
+/// Many base58 strings are also valid base64, so without an explicit
+/// encoding both are tried until one yields a transaction.
+fn parse_transaction(
+    text: &str,
+    encoding: Option<TxEncoding>,
+) -> Result<(VersionedTransaction, Vec<u8>)> {
+    let base64 = || -> Result<_> {
+        let bytes = BASE64_STANDARD
+            .decode(text)
+            .map_err(|e| anyhow::anyhow!("invalid base64: {}", e))?;
+        Ok((deserialize_transaction(&bytes)?, bytes))
+    };
+    let base58 = || -> Result<_> {
+        let bytes = bs58::decode(text)
+            .into_vec()
+            .map_err(|e| anyhow::anyhow!("invalid base58: {}", e))?;
+        Ok((deserialize_transaction(&bytes)?, bytes))
+    };
+    match encoding {
+        Some(TxEncoding::Base64) => base64().map_err(|e| anyhow::anyhow!("Base64 input: {}", e)),
+        Some(TxEncoding::Base58) => base58().map_err(|e| anyhow::anyhow!("Base58 input: {}", e)),
+        None => base64().or_else(|e64| {
+            base58().map_err(|e58| {
+                anyhow::anyhow!(
+                    "Input is not a transaction as base64 ({}) nor as base58 ({})",
+                    e64,
+                    e58
+                )
+            })
+        }),
+    }
+}
+
+/// Decode a serialized transaction offline, without contacting the cluster.
+///
+/// Shows the message header, each signature and whether it verifies, the
+/// account keys with their flags, the recent blockhash and the decoded
+/// instructions. Lookup table addresses of v0 transactions cannot be
+/// resolved offline and are shown as `<unresolved>`.
+pub fn decode(
+    input: Option<&str>,
+    file: Option<&Path>,
+    encoding: Option<TxEncoding>,
+) -> Result<()> {
+    let text = read_input(input, file)?;
+    let (tx, bytes) = parse_transaction(&text, encoding)?;
+
+    let message = ui_raw_message(&tx.message);
+    let keys = resolve_account_keys(&message, None);
+    let header = &message.header;
+
+    println!("\n{}", "--- Decoded Transaction ---".bold().cyan());
+    let version = match tx.version() {
+        TransactionVersion::Number(n) => format!("v{}", n),
+        TransactionVersion::Legacy(_) => "legacy".to_string(),
+    };
+    println!("{:<25} : {}", "Version", version);
+    println!("{:<25} : {} bytes", "Size", bytes.len());
+    println!(
+        "{:<25} : {}",
+        "Required Signatures", header.num_required_signatures
+    );
+    println!(
+        "{:<25} : {}",
+        "Readonly Signed", header.num_readonly_signed_accounts
+    );
+    println!(
+        "{:<25} : {}",
+        "Readonly Unsigned", header.num_readonly_unsigned_accounts
+    );
+    println!(
+        "{:<25} : {}",
+        "Recent Blockhash",
+        message.recent_blockhash.yellow()
+    );
+    if uses_durable_nonce(&tx.message) {
+        println!(
+            "{:<25} : {}",
+            "Nonce",
+            "Durable nonce (blockhash is the nonce value)".cyan()
+        );
+    }
+
+    println!(
+        "\n{}",
+        format!("--- Signatures ({}) ---", tx.signatures.len())
+            .bold()
+            .cyan()
+    );
+    let verified = tx.verify_with_results();
+    for (i, signature) in tx.signatures.iter().enumerate() {
+        let signer = keys
+            .get(i)
+            .map(|k| labels::display(&k.pubkey))
+            .unwrap_or_default();
+        let status = if *signature == Signature::default() {
+            "Missing (unsigned)".yellow()
+        } else if verified.get(i).copied().unwrap_or(false) {
+            "Valid".green()
+        } else {
+            "INVALID".red().bold()
+        };
+        println!("[{:02}] {} ({})", i, signer, status);
+        println!("     {}", signature);
+    }
+
+    print_account_keys(&keys);
+    print_instructions(&message, &keys);
+    Ok(())
+}
+
+/// Whether the first instruction advances a durable nonce account.
+fn uses_durable_nonce(message: &VersionedMessage) -> bool {
+    let Some(ix) = message.instructions().first() else {
+        return false;
+    };
+    message
+        .static_account_keys()
+        .get(ix.program_id_index as usize)
+        .is_some_and(system_program::check_id)
+        && matches!(
+            bincode::deserialize(&ix.data),
+            Ok(SystemInstruction::AdvanceNonceAccount)
+        )
+}
+
 /// Fetch and display detailed transaction information.
 ///
 /// This function retrieves the transaction details from the Solana cluster using the provided signature.
 /// It displays:
-/// - Signature, Slot, and Block Time (Local)
+/// - Signature, Slot, Version and Block Time (Local)
 /// - Execution Status (Success/Failure)
 /// - Compute Units Consumed
 /// - Fee (in lamports)
 /// - Transaction Logs
+/// - Account Keys, including addresses loaded from lookup tables
+/// - Decoded Instructions
 /// - Invoked Program IDs
 pub fn get_details(client: &RpcClient, sig_str: &str) -> Result<()> {
     let signature =
         Signature::from_str(sig_str).map_err(|_| anyhow::anyhow!("Invalid Signature format"))?;
+    let tx = fetch(client, &signature)?;
 
-    // Fetch transaction with JSON encoding to parse instructions/accounts easily
-    let tx = client.get_transaction_with_config(
-        &signature,
-        solana_client::rpc_config::RpcTransactionConfig {
-            encoding: Some(UiTransactionEncoding::Json),
-            commitment: Some(solana_sdk::commitment_config::CommitmentConfig::finalized()),
-            max_supported_transaction_version: Some(0),
-        },
-    )?;
+    let meta = tx.transaction.meta.as_ref();
+    let message = raw_message(&tx);
+    let keys = account_keys(&tx);
 
     println!("\n{}", "--- Transaction Details ---".bold().cyan());
     println!("{:<20} : {}", "Signature", sig_str.yellow());
     println!("{:<20} : {}", "Slot", tx.slot);
+    let version = match &tx.transaction.version {
+        Some(TransactionVersion::Number(n)) => format!("v{}", n),
+        _ => "legacy".to_string(),
+    };
+    println!("{:<20} : {}", "Version", version);
 
     // Fetch Block Time
     if let Some(time) = tx.block_time {
@@ -49,7 +538,7 @@ pub fn get_details(client: &RpcClient, sig_str: &str) -> Result<()> {
     }
 
     // Parse Transaction Meta
-    if let Some(meta) = &tx.transaction.meta {
+    if let Some(meta) = meta {
         // Handle OptionSerializer for Compute Units
         let units = match meta.compute_units_consumed {
             OptionSerializer::Some(val) => val,
@@ -77,58 +566,73 @@ pub fn get_details(client: &RpcClient, sig_str: &str) -> Result<()> {
         }
 
         // Display Inner Instructions
-        if let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions {
-            if !inner_instructions.is_empty() {
-                println!("\n{}", "--- Inner Instructions ---".bold().cyan());
-                for (idx, ix_list) in inner_instructions.iter().enumerate() {
-                    println!("  Program Instruction {}", idx);
-                    for (inner_idx, inner_ix) in ix_list.instructions.iter().enumerate() {
-                        // Handle UiInstruction Enum (Compiled vs parsed)
-                        match inner_ix {
-                            solana_transaction_status::UiInstruction::Compiled(compiled) => {
-                                // Program ID is an index in account keys, hard to resolve here without passing account keys map down.
-                                // simpler to print index
-                                println!("    [{:02}] Program Index: {}", inner_idx, compiled.program_id_index);
-                            }
-                            solana_transaction_status::UiInstruction::Parsed(parsed) => {
-                                match parsed {
-                                    solana_transaction_status::UiParsedInstruction::Parsed(p) => {
-                                        println!("    [{:02}] Program: {}", inner_idx, p.program.purple());
-                                    }
-                                    solana_transaction_status::UiParsedInstruction::PartiallyDecoded(pd) => {
-                                        println!("    [{:02}] Program ID: {}", inner_idx, pd.program_id.purple());
-                                    }
-                                }
+        if let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions
+            && !inner_instructions.is_empty()
+        {
+            println!("\n{}", "--- Inner Instructions ---".bold().cyan());
+            for (idx, ix_list) in inner_instructions.iter().enumerate() {
+                println!("  Program Instruction {}", idx);
+                for (inner_idx, inner_ix) in ix_list.instructions.iter().enumerate() {
+                    // Handle UiInstruction Enum (Compiled vs parsed)
+                    match inner_ix {
+                        solana_transaction_status::UiInstruction::Compiled(compiled) => {
+                            // Resolve the program through the full (lookup-expanded) key list
+                            match keys.get(compiled.program_id_index as usize) {
+                                Some(key) => println!(
+                                    "    [{:02}] Program: {}",
+                                    inner_idx,
+                                    labels::display(&key.pubkey).purple()
+                                ),
+                                None => println!(
+                                    "    [{:02}] Program Index: {}",
+                                    inner_idx, compiled.program_id_index
+                                ),
                             }
                         }
+                        solana_transaction_status::UiInstruction::Parsed(parsed) => match parsed {
+                            solana_transaction_status::UiParsedInstruction::Parsed(p) => {
+                                println!("    [{:02}] Program: {}", inner_idx, p.program.purple());
+                            }
+                            solana_transaction_status::UiParsedInstruction::PartiallyDecoded(
+                                pd,
+                            ) => {
+                                println!(
+                                    "    [{:02}] Program ID: {}",
+                                    inner_idx,
+                                    labels::display(&pd.program_id).purple()
+                                );
+                            }
+                        },
                     }
                 }
             }
         }
     }
 
+    // Display the full account list, with lookup table origins for v0 transactions
+    print_account_keys(&keys);
+
+    if let Some(msg) = message {
+        print_instructions(msg, &keys);
+    }
+
     // Extract and Display Program IDs involved in the transaction
-    if let EncodedTransaction::Json(ui_tx) = tx.transaction.transaction {
-        if let UiMessage::Raw(msg) = ui_tx.message {
-            println!("\n{}", "--- Involved Programs ---".bold().cyan());
-            let account_keys = msg.account_keys;
-
-            // Collect unique program IDs from instructions
-            let mut programs = Vec::new();
-            for ix in msg.instructions {
-                let program_idx = ix.program_id_index as usize;
-                if program_idx < account_keys.len() {
-                    let prog_id = &account_keys[program_idx];
-                    if !programs.contains(prog_id) {
-                        programs.push(prog_id.clone());
-                    }
-                }
-            }
+    if let Some(msg) = message {
+        println!("\n{}", "--- Involved Programs ---".bold().cyan());
 
-            for prog in programs {
-                println!("- {}", prog.magenta());
+        // Collect unique program IDs from instructions
+        let mut programs: Vec<&str> = Vec::new();
+        for ix in &msg.instructions {
+            if let Some(key) = keys.get(ix.program_id_index as usize)
+                && !programs.contains(&key.pubkey.as_str())
+            {
+                programs.push(&key.pubkey);
             }
         }
+
+        for prog in programs {
+            println!("- {}", labels::display(prog).magenta());
+        }
     }
 
     println!("{}\n", "---------------------------".bold().cyan());
@@ -136,27 +640,89 @@ pub fn get_details(client: &RpcClient, sig_str: &str) -> Result<()> {
     Ok(())
 }
 
+/// Print the logs of a transaction, indented by invocation depth.
 pub fn get_logs(client: &RpcClient, sig_str: &str) -> Result<()> {
-    // Similar to details but only prints logs
     let signature = Signature::from_str(sig_str).map_err(|_| anyhow::anyhow!("Invalid Sig"))?;
-    let tx = client.get_transaction_with_config(
-        &signature,
-        solana_client::rpc_config::RpcTransactionConfig {
-            encoding: Some(UiTransactionEncoding::Json),
-            commitment: Some(solana_sdk::commitment_config::CommitmentConfig::finalized()),
-            max_supported_transaction_version: Some(0),
-        },
-    )?;
+    let tx = fetch(client, &signature)?;
 
     if let Some(meta) = tx.transaction.meta {
         if let OptionSerializer::Some(logs) = meta.log_messages {
             println!("\n{}", "--- Transaction Logs ---".bold().cyan());
-            for (i, log) in logs.iter().enumerate() {
-                println!("[{:02}] {}", i, log);
-            }
+            logs::print_logs(&logs);
         } else {
             println!("No logs found.");
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::message::{Message, v0};
+    use solana_sdk::signer::Signer;
+    use solana_sdk::signer::keypair::{Keypair, keypair_from_seed};
+    use solana_sdk::transaction::Transaction;
+
+    /// Signed legacy and v0 transactions with instruction data of `len` bytes.
+    fn transactions(payer: &Keypair, len: usize) -> [VersionedTransaction; 2] {
+        let ix = Instruction::new_with_bytes(
+            system_program::id(),
+            &vec![7; len],
+            vec![AccountMeta::new(payer.pubkey(), true)],
+        );
+        let message = Message::new_with_blockhash(
+            std::slice::from_ref(&ix),
+            Some(&payer.pubkey()),
+            &Hash::default(),
+        );
+        let legacy = Transaction::new(&[payer], message, Hash::default());
+        let message =
+            v0::Message::try_compile(&payer.pubkey(), &[ix], &[], Hash::default()).unwrap();
+        let v0 = VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer]).unwrap();
+        [legacy.into(), v0]
+    }
+
+    #[test]
+    fn parse_transaction_round_trips_both_encodings() {
+        let payer = keypair_from_seed(&[1; 32]).unwrap();
+        let mut base58_valid_as_base64 = 0;
+        for len in 0..8 {
+            for tx in transactions(&payer, len) {
+                let bytes = bincode::serialize(&tx).unwrap();
+                let base64 = BASE64_STANDARD.encode(&bytes);
+                let base58 = bs58::encode(&bytes).into_string();
+                if BASE64_STANDARD.decode(&base58).is_ok() {
+                    base58_valid_as_base64 += 1;
+                }
+                for (text, encoding) in [
+                    (&base64, None),
+                    (&base64, Some(TxEncoding::Base64)),
+                    (&base58, None),
+                    (&base58, Some(TxEncoding::Base58)),
+                ] {
+                    let (decoded, decoded_bytes) = parse_transaction(text, encoding).unwrap();
+                    assert_eq!(decoded, tx);
+                    assert_eq!(decoded_bytes, bytes);
+                }
+            }
+        }
+        // Auto-detection must not stop at base58 text that is also base64
+        assert!(base58_valid_as_base64 > 0);
+    }
+
+    #[test]
+    fn parse_transaction_reports_both_errors() {
+        let err = parse_transaction("not a transaction", None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("base64"));
+        assert!(err.contains("base58"));
+        let payer = keypair_from_seed(&[1; 32]).unwrap();
+        let bytes = bincode::serialize(&transactions(&payer, 1)[0]).unwrap();
+        let base58 = bs58::encode(&bytes).into_string();
+        assert!(parse_transaction(&base58, Some(TxEncoding::Base64)).is_err());
+    }
+}
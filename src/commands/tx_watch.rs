@@ -0,0 +1,306 @@
+use crate::commands::transaction;
+use crate::rpc;
+use anyhow::Result;
+use colored::*;
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSignatureSubscribeConfig;
+use solana_client::rpc_response::RpcSignatureResult;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{TransactionConfirmationStatus, TransactionStatus};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Delay between signature status polls.
+const POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Confirmation stages of a transaction, in the order they are reached.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Stage {
+    NotFound,
+    /// Seen by the RPC node but not yet executed (websocket only).
+    Received,
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Stage {
+    fn label(self) -> ColoredString {
+        match self {
+            Stage::NotFound => "Not Found".red(),
+            Stage::Received => "Received".cyan(),
+            Stage::Processed => "Processed".yellow(),
+            Stage::Confirmed => "Confirmed".blue(),
+            Stage::Finalized => "Finalized".green(),
+        }
+    }
+}
+
+/// Stage reported by a `getSignatureStatuses` result.
+fn stage_of(status: &TransactionStatus) -> Stage {
+    match status.confirmation_status {
+        Some(TransactionConfirmationStatus::Finalized) => Stage::Finalized,
+        Some(TransactionConfirmationStatus::Confirmed) => Stage::Confirmed,
+        _ => Stage::Processed,
+    }
+}
+
+/// Why a transaction that is not visible can no longer land, if it cannot.
+///
+/// `blockhash` pairs the watched blockhash with whether it is still valid.
+fn expiry(height: u64, last_valid_height: u64, blockhash: Option<(&Hash, bool)>) -> Option<String> {
+    if height > last_valid_height {
+        return Some(format!(
+            "block height {} passed {} without it landing",
+            height, last_valid_height
+        ));
+    }
+    match blockhash {
+        Some((hash, false)) => Some(format!("blockhash {} is no longer valid", hash)),
+        _ => None,
+    }
+}
+
+/// Status of `signature`, searching the ledger history instead of only the
+/// node's recent status cache when `history` is set.
+fn fetch_status(
+    client: &RpcClient,
+    signature: &Signature,
+    history: bool,
+) -> Result<Option<TransactionStatus>> {
+    let statuses = if history {
+        client.get_signature_statuses_with_history(&[*signature])?
+    } else {
+        client.get_signature_statuses(&[*signature])?
+    };
+    Ok(statuses.value.into_iter().next().flatten())
+}
+
+/// Progress of a watched signature; prints each stage as it is reached.
+struct Progress {
+    start: Instant,
+    /// Latest stage printed, `None` before the first observation.
+    stage: Option<Stage>,
+    failed: bool,
+}
+
+impl Progress {
+    /// Record an observation, printing it when it is a later stage.
+    ///
+    /// `confirmations` is `None` once the slot is rooted.
+    fn advance(&mut self, stage: Stage, slot: Option<u64>, confirmations: Option<usize>) {
+        if Some(stage) <= self.stage {
+            return;
+        }
+        self.stage = Some(stage);
+        let slot = slot.map_or("-".to_string(), |s| s.to_string());
+        let confirmations = match (stage, confirmations) {
+            (Stage::NotFound | Stage::Received, _) => "-".to_string(),
+            (_, Some(n)) => n.to_string(),
+            (_, None) => "max (rooted)".to_string(),
+        };
+        println!(
+            "{} {:>9}  {:<10} slot {:<12} confirmations {}",
+            chrono::Utc::now().format("%H:%M:%S%.3f"),
+            format!("+{:.2}s", self.start.elapsed().as_secs_f64()),
+            stage.label(),
+            slot,
+            confirmations
+        );
+    }
+
+    fn fail(&mut self, err: impl std::fmt::Debug) {
+        if !self.failed {
+            self.failed = true;
+            println!("{:<25} : {} ({:?})", "Status", "FAILED".red().bold(), err);
+        }
+    }
+}
+
+/// Follow a signature from not found through processed, confirmed and finalized.
+///
+/// A `signatureSubscribe` websocket reports when the node receives and
+/// processes the transaction, while `getSignatureStatuses` polls track the
+/// commitment level and confirmation count. The first poll and the check
+/// before giving up also search the ledger history, so signatures older
+/// than the node's status cache are still found. Watching stops with an error
+/// once the transaction can no longer land: when the block height passes
+/// the last valid height of the newest blockhash at start (no transaction
+/// can use a newer one), or as soon as `blockhash`, if given, expires.
+/// The finalized transaction is then shown with `tx-info`.
+pub fn watch(rpc_url: &str, sig_str: &str, blockhash: Option<&str>) -> Result<()> {
+    let signature =
+        Signature::from_str(sig_str).map_err(|_| anyhow::anyhow!("Invalid Signature format"))?;
+    let blockhash = blockhash
+        .map(|h| Hash::from_str(h).map_err(|_| anyhow::anyhow!("Invalid blockhash")))
+        .transpose()?;
+    let client = RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::processed());
+    let (_, last_valid_height) =
+        client.get_latest_blockhash_with_commitment(CommitmentConfig::processed())?;
+
+    println!("\n{}", "--- Watching Transaction ---".bold().cyan());
+    println!("{:<25} : {}", "Signature", sig_str.yellow());
+    println!("{:<25} : {}", "Expires After Height", last_valid_height);
+
+    let ws_url = rpc::ws_url(rpc_url);
+    let config = RpcSignatureSubscribeConfig {
+        commitment: Some(CommitmentConfig::processed()),
+        enable_received_notification: Some(true),
+    };
+    let mut subscription =
+        match PubsubClient::signature_subscribe(&ws_url, &signature, Some(config)) {
+            Ok(subscription) => {
+                println!("{:<25} : {}", "WebSocket", ws_url);
+                Some(subscription)
+            }
+            Err(e) => {
+                println!(
+                    "{:<25} : {} ({})",
+                    "WebSocket",
+                    "Unavailable, polling only".yellow(),
+                    e
+                );
+                None
+            }
+        };
+    println!();
+
+    let mut progress = Progress {
+        start: Instant::now(),
+        stage: None,
+        failed: false,
+    };
+    let result = loop {
+        // Wait for a websocket notification, which doubles as the poll delay
+        if let Some((_, receiver)) = &subscription {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(update) => match update.value {
+                    RpcSignatureResult::ReceivedSignature(_) => {
+                        progress.advance(Stage::Received, None, None)
+                    }
+                    RpcSignatureResult::ProcessedSignature(processed) => {
+                        progress.advance(Stage::Processed, Some(update.context.slot), Some(0));
+                        if let Some(err) = processed.err {
+                            progress.fail(err);
+                        }
+                    }
+                },
+                // The server cancels the one-shot subscription once it has
+                // notified; from then on the receiver returns immediately
+                Err(e) if e.is_disconnected() => {
+                    if let Some((closed, _)) = subscription.take() {
+                        rpc::close_subscription(closed);
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+        if subscription.is_none() {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+
+        let mut status = fetch_status(&client, &signature, progress.stage.is_none())?;
+        if status.is_none() {
+            progress.advance(Stage::NotFound, None, None);
+            // Not (or no longer) visible: give up once it can never land
+            let height = client.get_block_height()?;
+            let valid = match &blockhash {
+                Some(hash) => Some((
+                    hash,
+                    client.is_blockhash_valid(hash, CommitmentConfig::processed())?,
+                )),
+                None => None,
+            };
+            let Some(reason) = expiry(height, last_valid_height, valid) else {
+                continue;
+            };
+            // It may have landed long enough ago to have left the status cache
+            status = fetch_status(&client, &signature, true)?;
+            if status.is_none() {
+                break Err(anyhow::anyhow!("Transaction expired: {}", reason));
+            }
+        }
+        let Some(status) = status else { continue };
+        let stage = stage_of(&status);
+        progress.advance(stage, Some(status.slot), status.confirmations);
+        if let Some(err) = status.err {
+            progress.fail(err);
+        }
+        if stage == Stage::Finalized {
+            break Ok(());
+        }
+    };
+
+    if let Some((subscription, _)) = subscription {
+        rpc::close_subscription(subscription);
+    }
+    result?;
+
+    println!(
+        "{:<25} : {}",
+        "Time To Finalize",
+        format!("{:.2}s", progress.start.elapsed().as_secs_f64()).green()
+    );
+    transaction::get_details(&rpc::get_client(rpc_url), sig_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(confirmation_status: Option<TransactionConfirmationStatus>) -> TransactionStatus {
+        TransactionStatus {
+            slot: 10,
+            confirmations: None,
+            status: Ok(()),
+            err: None,
+            confirmation_status,
+        }
+    }
+
+    #[test]
+    fn stage_follows_confirmation_status() {
+        assert!(stage_of(&status(None)) == Stage::Processed);
+        assert!(
+            stage_of(&status(Some(TransactionConfirmationStatus::Processed))) == Stage::Processed
+        );
+        assert!(
+            stage_of(&status(Some(TransactionConfirmationStatus::Confirmed))) == Stage::Confirmed
+        );
+        assert!(
+            stage_of(&status(Some(TransactionConfirmationStatus::Finalized))) == Stage::Finalized
+        );
+    }
+
+    #[test]
+    fn progress_only_moves_forward() {
+        let mut progress = Progress {
+            start: Instant::now(),
+            stage: None,
+            failed: false,
+        };
+        progress.advance(Stage::NotFound, None, None);
+        progress.advance(Stage::Confirmed, Some(10), Some(3));
+        // A lagging poll must not move the reported stage back
+        progress.advance(Stage::Processed, Some(10), Some(0));
+        assert!(progress.stage == Some(Stage::Confirmed));
+        progress.advance(Stage::Finalized, Some(10), None);
+        assert!(progress.stage == Some(Stage::Finalized));
+    }
+
+    #[test]
+    fn expiry_by_height_or_blockhash() {
+        let hash = Hash::default();
+        assert_eq!(expiry(100, 100, None), None);
+        assert_eq!(expiry(100, 100, Some((&hash, true))), None);
+        assert!(expiry(101, 100, None).unwrap().contains("passed 100"));
+        assert!(
+            expiry(50, 100, Some((&hash, false)))
+                .unwrap()
+                .contains("no longer valid")
+        );
+    }
+}
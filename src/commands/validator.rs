@@ -1,20 +1,160 @@
+use crate::decode;
+use crate::labels;
+use crate::utils;
 use anyhow::Result;
 use colored::*;
 use prettytable::{Cell, Row, Table};
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcBlockProductionConfig, RpcBlockProductionConfigRange};
+use solana_client::rpc_response::RpcVoteAccountInfo;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::vote;
+use std::str::FromStr;
 
-pub fn get_validators(client: &RpcClient) -> Result<()> {
+/// Sort orders for `validator-list`.
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum ValidatorSort {
+    /// Most activated stake first.
+    Stake,
+    /// Lowest commission first.
+    Commission,
+    /// Most vote credits earned this epoch first.
+    Credits,
+    /// Most recent vote first.
+    LastVote,
+}
+
+/// Command-line options for `validator-list`.
+#[derive(clap::Args)]
+pub struct ValidatorArgs {
+    #[arg(long, value_enum, default_value_t = ValidatorSort::Stake)]
+    pub sort: ValidatorSort,
+
+    /// Maximum number of validators to list.
+    #[arg(long, default_value_t = 20, conflicts_with = "all")]
+    pub limit: usize,
+
+    /// List every matching validator.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Only list delinquent validators.
+    #[arg(long)]
+    pub delinquent: bool,
+
+    /// Only list validators with at most this commission (percent).
+    #[arg(long)]
+    pub max_commission: Option<u8>,
+}
+
+/// Vote credits earned in the most recent epoch of the history.
+fn current_credits(vote: &RpcVoteAccountInfo) -> u64 {
+    vote.epoch_credits
+        .last()
+        .map(|(_, credits, prev)| credits.saturating_sub(*prev))
+        .unwrap_or(0)
+}
+
+/// Smallest number of validators that together hold more than a third of
+/// the stake (the superminority able to halt the cluster).
+fn nakamoto_coefficient(stakes: &mut [u64]) -> usize {
+    let total: u128 = stakes.iter().map(|s| *s as u128).sum();
+    stakes.sort_unstable_by(|a, b| b.cmp(a));
+    let mut held = 0u128;
+    for (i, stake) in stakes.iter().enumerate() {
+        held += *stake as u128;
+        if held * 3 > total {
+            return i + 1;
+        }
+    }
+    stakes.len()
+}
+
+/// List validators with stake share, cumulative stake and vote activity.
+///
+/// Stake percentages are relative to all activated stake, delinquent
+/// included; the cumulative column follows the listed order.
+pub fn get_validators(client: &RpcClient, args: &ValidatorArgs) -> Result<()> {
     println!("Fetching validator set...");
     let vote_accounts = client.get_vote_accounts()?;
+    let total_stake: u64 = vote_accounts
+        .current
+        .iter()
+        .chain(&vote_accounts.delinquent)
+        .map(|v| v.activated_stake)
+        .sum();
+    let delinquent_stake: u64 = vote_accounts
+        .delinquent
+        .iter()
+        .map(|v| v.activated_stake)
+        .sum();
+    let mut stakes: Vec<u64> = vote_accounts
+        .current
+        .iter()
+        .chain(&vote_accounts.delinquent)
+        .map(|v| v.activated_stake)
+        .collect();
+    let nakamoto = nakamoto_coefficient(&mut stakes);
+
+    let mut validators: Vec<(&RpcVoteAccountInfo, bool)> = if args.delinquent {
+        vote_accounts.delinquent.iter().map(|v| (v, true)).collect()
+    } else {
+        vote_accounts
+            .current
+            .iter()
+            .map(|v| (v, false))
+            .chain(vote_accounts.delinquent.iter().map(|v| (v, true)))
+            .collect()
+    };
+    if let Some(max) = args.max_commission {
+        validators.retain(|(v, _)| v.commission <= max);
+    }
+    match args.sort {
+        ValidatorSort::Stake => {
+            validators.sort_by_key(|(v, _)| std::cmp::Reverse(v.activated_stake))
+        }
+        ValidatorSort::Commission => {
+            validators.sort_by_key(|(v, _)| (v.commission, std::cmp::Reverse(v.activated_stake)))
+        }
+        ValidatorSort::Credits => {
+            validators.sort_by_key(|(v, _)| std::cmp::Reverse(current_credits(v)))
+        }
+        ValidatorSort::LastVote => validators.sort_by_key(|(v, _)| std::cmp::Reverse(v.last_vote)),
+    }
+
+    println!("\n{}", "--- Validator Set ---".bold().cyan());
+    println!(
+        "{:<25} : {} ({} delinquent)",
+        "Validators",
+        vote_accounts.current.len() + vote_accounts.delinquent.len(),
+        vote_accounts.delinquent.len().to_string().red()
+    );
+    println!(
+        "{:<25} : {} SOL",
+        "Total Active Stake",
+        utils::format_sol(total_stake as i128)
+    );
+    println!(
+        "{:<25} : {:.2}%",
+        "Delinquent Stake",
+        utils::percent(delinquent_stake, total_stake)
+    );
+    println!(
+        "{:<25} : {}",
+        "Nakamoto Coefficient",
+        nakamoto.to_string().yellow().bold()
+    );
 
+    let shown = if args.all {
+        validators.len()
+    } else {
+        args.limit.min(validators.len())
+    };
     println!(
         "\n{}",
-        format!(
-            "--- Active Validators ({}) ---",
-            vote_accounts.current.len()
-        )
-        .bold()
-        .cyan()
+        format!("--- Validators ({} of {}) ---", shown, validators.len())
+            .bold()
+            .cyan()
     );
 
     let mut table = Table::new();
@@ -23,26 +163,232 @@ pub fn get_validators(client: &RpcClient) -> Result<()> {
         Cell::new("Vote Pubkey").style_spec("Fy"),
         Cell::new("Commission").style_spec("Fc"),
         Cell::new("Activated Stake").style_spec("Fg"),
+        Cell::new("% Stake").style_spec("Fg"),
+        Cell::new("Cumulative").style_spec("Fg"),
+        Cell::new("Credits").style_spec("Fc"),
+        Cell::new("Last Vote").style_spec("Fy"),
     ]));
-
-    // Sort by stake details? They come somewhat sorted.
-    // Take top 20
-    for vote in vote_accounts.current.iter().take(20) {
+    let mut cumulative = 0u64;
+    for (vote, delinquent) in validators.iter().take(shown) {
+        cumulative += vote.activated_stake;
+        let node = Cell::new(&labels::display(&vote.node_pubkey));
         table.add_row(Row::new(vec![
-            Cell::new(&vote.node_pubkey),
-            Cell::new(&vote.vote_pubkey),
+            if *delinquent {
+                node.style_spec("Fr")
+            } else {
+                node
+            },
+            Cell::new(&labels::display(&vote.vote_pubkey)),
             Cell::new(&format!("{}%", vote.commission)),
             Cell::new(&format!(
                 "{:.2} SOL",
                 vote.activated_stake as f64 / 1_000_000_000.0
             )),
+            Cell::new(&format!(
+                "{:.2}%",
+                utils::percent(vote.activated_stake, total_stake)
+            )),
+            Cell::new(&format!("{:.2}%", utils::percent(cumulative, total_stake))),
+            Cell::new(&current_credits(vote).to_string()),
+            Cell::new(&vote.last_vote.to_string()),
         ]));
     }
 
     table.printstd();
-    if vote_accounts.current.len() > 20 {
-        println!("... and {} more.", vote_accounts.current.len() - 20);
+    if validators.len() > shown {
+        println!(
+            "... and {} more (use --all or --limit).",
+            validators.len() - shown
+        );
+    }
+    if validators.iter().take(shown).any(|(_, d)| *d) {
+        println!("{}", "Delinquent validators are shown in red.".red());
+    }
+
+    Ok(())
+}
+
+/// Decode a vote account (Identity, Authorities, Commission, Credits).
+pub fn get_vote_account(client: &RpcClient, address: &str) -> Result<()> {
+    let pubkey =
+        Pubkey::from_str(address).map_err(|_| anyhow::anyhow!("Invalid Vote Account Address"))?;
+    let account = client.get_account(&pubkey)?;
+    if account.owner != vote::program::id() {
+        anyhow::bail!(
+            "Account is not a vote account (owner: {})",
+            labels::display(&account.owner.to_string())
+        );
     }
+    let fields = decode::vote_account(&account.data)?;
 
+    println!("\n{}", "--- Vote Account Details ---".bold().cyan());
+    println!("{:<25} : {}", "Address", address.yellow());
+    if let Some(label) = labels::lookup(address) {
+        println!("{:<25} : {}", "Label", label.cyan());
+    }
+    println!(
+        "{:<25} : {:.9} SOL",
+        "Balance",
+        account.lamports as f64 / 1_000_000_000.0
+    );
+    decode::print_fields(&fields);
+    Ok(())
+}
+
+/// Sort orders for the block production report.
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum ProductionSort {
+    /// Highest skip rate first.
+    SkipRate,
+    /// Most leader slots first.
+    Slots,
+    /// Most skipped slots first.
+    Skipped,
+}
+
+/// Command-line options for `block-production`.
+#[derive(clap::Args)]
+pub struct ProductionArgs {
+    /// Report on this epoch (defaults to the current epoch).
+    #[arg(long, conflicts_with_all = ["first_slot", "last_slot"])]
+    pub epoch: Option<u64>,
+
+    /// First slot of a custom range.
+    #[arg(long, requires = "last_slot")]
+    pub first_slot: Option<u64>,
+
+    /// Last slot of a custom range.
+    #[arg(long)]
+    pub last_slot: Option<u64>,
+
+    /// Only report on this validator identity.
+    #[arg(long)]
+    pub identity: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = ProductionSort::SkipRate)]
+    pub sort: ProductionSort,
+
+    /// Maximum number of validators to list (0 for all).
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+}
+
+/// Leader slots, produced blocks and skip rate per validator.
+///
+/// Covers an epoch or an explicit slot range; ranges that reach past the
+/// current slot are cut off there. Validators skipping more than the
+/// cluster-wide rate are highlighted.
+pub fn get_block_production(client: &RpcClient, args: &ProductionArgs) -> Result<()> {
+    let current_slot = client.get_slot()?;
+    let range = match (args.epoch, args.first_slot, args.last_slot) {
+        (Some(epoch), ..) => {
+            let schedule = client.get_epoch_schedule()?;
+            let first = schedule.get_first_slot_in_epoch(epoch);
+            if first > current_slot {
+                anyhow::bail!("Epoch {} has not started yet", epoch);
+            }
+            Some(RpcBlockProductionConfigRange {
+                first_slot: first,
+                last_slot: Some(schedule.get_last_slot_in_epoch(epoch).min(current_slot)),
+            })
+        }
+        (None, Some(first), last) => Some(RpcBlockProductionConfigRange {
+            first_slot: first,
+            last_slot: last.map(|l| l.min(current_slot)),
+        }),
+        _ => None,
+    };
+    let production = client
+        .get_block_production_with_config(RpcBlockProductionConfig {
+            identity: args.identity.clone(),
+            range,
+            commitment: None,
+        })?
+        .value;
+
+    let mut rows: Vec<(&String, usize, usize)> = production
+        .by_identity
+        .iter()
+        .map(|(id, (slots, produced))| (id, *slots, *produced))
+        .collect();
+    let skip_rate = |slots: usize, produced: usize| {
+        if slots == 0 {
+            0.0
+        } else {
+            (slots - produced) as f64 / slots as f64 * 100.0
+        }
+    };
+    match args.sort {
+        ProductionSort::SkipRate => rows.sort_by(|a, b| {
+            skip_rate(b.1, b.2)
+                .total_cmp(&skip_rate(a.1, a.2))
+                .then(b.1.cmp(&a.1))
+        }),
+        ProductionSort::Slots => rows.sort_by_key(|r| std::cmp::Reverse(r.1)),
+        ProductionSort::Skipped => rows.sort_by_key(|r| std::cmp::Reverse(r.1 - r.2)),
+    }
+
+    let total_slots: usize = rows.iter().map(|r| r.1).sum();
+    let total_produced: usize = rows.iter().map(|r| r.2).sum();
+    let cluster_rate = skip_rate(total_slots, total_produced);
+    let mean_rate = if rows.is_empty() {
+        0.0
+    } else {
+        rows.iter().map(|r| skip_rate(r.1, r.2)).sum::<f64>() / rows.len() as f64
+    };
+
+    println!("\n{}", "--- Block Production ---".bold().cyan());
+    println!(
+        "{:<25} : {} - {}",
+        "Slot Range", production.range.first_slot, production.range.last_slot
+    );
+    println!("{:<25} : {}", "Validators", rows.len());
+    println!("{:<25} : {}", "Leader Slots", total_slots);
+    println!("{:<25} : {}", "Blocks Produced", total_produced);
+    println!("{:<25} : {}", "Skipped Slots", total_slots - total_produced);
+    println!(
+        "{:<25} : {}",
+        "Cluster Skip Rate",
+        format!("{:.2}%", cluster_rate).yellow().bold()
+    );
+    println!("{:<25} : {:.2}%", "Avg Validator Skip Rate", mean_rate);
+
+    if rows.is_empty() {
+        println!("No leader slots in this range.");
+        return Ok(());
+    }
+
+    let shown = if args.limit == 0 {
+        rows.len()
+    } else {
+        args.limit.min(rows.len())
+    };
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Identity").style_spec("Fm"),
+        Cell::new("Leader Slots").style_spec("Fy"),
+        Cell::new("Produced").style_spec("Fg"),
+        Cell::new("Skipped").style_spec("Fr"),
+        Cell::new("Skip Rate").style_spec("Fc"),
+    ]));
+    for (identity, slots, produced) in &rows[..shown] {
+        let rate = skip_rate(*slots, *produced);
+        let rate_cell = Cell::new(&format!("{:.2}%", rate));
+        table.add_row(Row::new(vec![
+            Cell::new(&labels::display(identity)),
+            Cell::new(&slots.to_string()),
+            Cell::new(&produced.to_string()),
+            Cell::new(&(slots - produced).to_string()),
+            if rate > cluster_rate {
+                rate_cell.style_spec("Fr")
+            } else {
+                rate_cell
+            },
+        ]));
+    }
+    table.printstd();
+    if rows.len() > shown {
+        println!("... and {} more.", rows.len() - shown);
+    }
     Ok(())
 }
@@ -0,0 +1,293 @@
+use anyhow::Result;
+use colored::*;
+use prettytable::{Cell, Row, Table};
+use std::time::{Duration, Instant};
+
+/// Named readings taken by one refresh of a watched view.
+pub type Metrics = Vec<(&'static str, f64)>;
+
+/// Refreshes shown in the session history table.
+const HISTORY_ROWS: usize = 10;
+
+/// Command-line option to keep a view refreshing in place.
+#[derive(clap::Args)]
+pub struct WatchArgs {
+    /// Refresh every SECS seconds (default 5), showing changes since the
+    /// last refresh and the session history.
+    #[arg(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "5")]
+    pub watch: Option<u64>,
+}
+
+/// One refresh of the session history.
+struct Sample {
+    time: chrono::DateTime<chrono::Utc>,
+    metrics: Metrics,
+}
+
+/// Refreshes of a watch session.
+#[derive(Default)]
+struct Session {
+    /// Successful refreshes, oldest first.
+    history: Vec<Sample>,
+    /// Every refresh attempted, failed ones included.
+    refreshes: usize,
+}
+
+impl Session {
+    /// Count a refresh, keeping its readings when it succeeded.
+    fn record(
+        &mut self,
+        time: chrono::DateTime<chrono::Utc>,
+        result: Result<Metrics>,
+    ) -> Result<()> {
+        self.refreshes += 1;
+        let metrics = result?;
+        self.history.push(Sample { time, metrics });
+        Ok(())
+    }
+}
+
+/// Change of one reading since the previous refresh and the first one.
+struct Change {
+    name: &'static str,
+    last: Option<f64>,
+    session: Option<f64>,
+}
+
+/// Session minimum, maximum and average of one reading.
+struct Stats {
+    min: f64,
+    max: f64,
+    avg: f64,
+}
+
+/// Show a view once, or keep refreshing it with `--watch`.
+///
+/// `render` prints the view and returns its readings. When watching, the
+/// screen is cleared before each refresh and the view is followed by the
+/// change of every reading since the previous refresh and since the
+/// session started, plus a table of recent refreshes. A failed refresh is
+/// reported and retried on the next interval instead of ending the session.
+pub fn run(args: &WatchArgs, mut render: impl FnMut() -> Result<Metrics>) -> Result<()> {
+    let Some(interval) = args.watch else {
+        return render().map(|_| ());
+    };
+    let interval = Duration::from_secs(interval.max(1));
+    let mut session = Session::default();
+    loop {
+        let started = Instant::now();
+        // Clear the screen and move the cursor home
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "{}",
+            format!(
+                "Every {}s · refresh #{} · {} · Ctrl+C to stop",
+                interval.as_secs(),
+                session.refreshes + 1,
+                chrono::Utc::now().format("%H:%M:%S UTC")
+            )
+            .dimmed()
+        );
+        let result = render();
+        match session.record(chrono::Utc::now(), result) {
+            Ok(()) => {
+                print_changes(&session.history);
+                print_history(&session.history);
+            }
+            Err(e) => println!("\n{} {}", "Refresh failed:".red().bold(), e),
+        }
+        std::thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
+/// Format a reading without decimals when it is whole.
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+fn format_delta(delta: f64) -> ColoredString {
+    let text = format_value(delta);
+    if delta > 0.0 {
+        format!("+{}", text).green()
+    } else if delta < 0.0 {
+        text.red()
+    } else {
+        text.normal()
+    }
+}
+
+fn reading(sample: &Sample, name: &str) -> Option<f64> {
+    sample
+        .metrics
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| *v)
+}
+
+/// Seconds since the previous refresh and the change of each latest reading,
+/// or `None` before the second refresh.
+fn changes(history: &[Sample]) -> Option<(f64, Vec<Change>)> {
+    let [.., previous, latest] = history else {
+        return None;
+    };
+    let first = &history[0];
+    let elapsed = (latest.time - previous.time).num_milliseconds() as f64 / 1000.0;
+    let changes = latest
+        .metrics
+        .iter()
+        .map(|(name, value)| Change {
+            name,
+            last: reading(previous, name).map(|v| value - v),
+            session: reading(first, name).map(|v| value - v),
+        })
+        .collect();
+    Some((elapsed, changes))
+}
+
+fn stats(history: &[Sample], name: &str) -> Option<Stats> {
+    let values: Vec<f64> = history.iter().filter_map(|s| reading(s, name)).collect();
+    if values.is_empty() {
+        return None;
+    }
+    Some(Stats {
+        min: values.iter().copied().fold(f64::INFINITY, f64::min),
+        max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        avg: values.iter().sum::<f64>() / values.len() as f64,
+    })
+}
+
+fn print_changes(history: &[Sample]) {
+    let Some((elapsed, changes)) = changes(history) else {
+        return;
+    };
+    println!(
+        "\n{}",
+        format!("--- Changes (last {:.0}s / session) ---", elapsed)
+            .bold()
+            .cyan()
+    );
+    for change in changes {
+        let show = |delta: Option<f64>| delta.map_or("-".normal(), format_delta);
+        println!(
+            "{:<25} : {} / {}",
+            change.name,
+            show(change.last),
+            show(change.session)
+        );
+    }
+}
+
+/// Recent refreshes with the session minimum, average and maximum.
+fn print_history(history: &[Sample]) {
+    let Some(latest) = history.last() else {
+        return;
+    };
+    println!(
+        "\n{}",
+        format!("--- Session History ({} refreshes) ---", history.len())
+            .bold()
+            .cyan()
+    );
+    let names: Vec<&str> = latest.metrics.iter().map(|(n, _)| *n).collect();
+    let mut table = Table::new();
+    let mut header = vec![Cell::new("Time").style_spec("Fy")];
+    header.extend(names.iter().map(|n| Cell::new(n).style_spec("Fc")));
+    table.add_row(Row::new(header));
+    for sample in &history[history.len().saturating_sub(HISTORY_ROWS)..] {
+        let mut row = vec![Cell::new(&sample.time.format("%H:%M:%S").to_string())];
+        row.extend(
+            names
+                .iter()
+                .map(|n| Cell::new(&reading(sample, n).map_or("-".to_string(), format_value))),
+        );
+        table.add_row(Row::new(row));
+    }
+    let stats: Vec<Option<Stats>> = names.iter().map(|n| stats(history, n)).collect();
+    for (label, pick) in [
+        ("Min", (|s: &Stats| s.min) as fn(&Stats) -> f64),
+        ("Max", |s: &Stats| s.max),
+        ("Avg", |s: &Stats| (s.avg * 100.0).round() / 100.0),
+    ] {
+        let mut row = vec![Cell::new(label).style_spec("Fm")];
+        row.extend(stats.iter().map(|s| {
+            Cell::new(
+                &s.as_ref()
+                    .map_or("-".to_string(), |s| format_value(pick(s))),
+            )
+        }));
+        table.add_row(Row::new(row));
+    }
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(secs: i64) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn failed_refreshes_are_counted_but_not_kept() {
+        let mut session = Session::default();
+        assert!(session.record(at(0), Ok(vec![("Slot", 100.0)])).is_ok());
+        assert!(
+            session
+                .record(at(5), Err(anyhow::anyhow!("timeout")))
+                .is_err()
+        );
+        assert!(session.record(at(10), Ok(vec![("Slot", 130.0)])).is_ok());
+        assert_eq!(session.refreshes, 3);
+        assert_eq!(session.history.len(), 2);
+
+        // The second successful refresh is compared with the first
+        let (elapsed, changes) = changes(&session.history).unwrap();
+        assert_eq!(elapsed, 10.0);
+        assert_eq!(changes[0].last, Some(30.0));
+    }
+
+    #[test]
+    fn changes_since_previous_and_first_refresh() {
+        let mut session = Session::default();
+        session.record(at(0), Ok(vec![("Slot", 100.0)])).unwrap();
+        assert!(changes(&session.history).is_none());
+
+        session
+            .record(at(5), Ok(vec![("Slot", 110.0), ("Lag", 2.0)]))
+            .unwrap();
+        session
+            .record(at(10), Ok(vec![("Slot", 105.0), ("Lag", 3.0)]))
+            .unwrap();
+        let (elapsed, changes) = changes(&session.history).unwrap();
+        assert_eq!(elapsed, 5.0);
+        assert_eq!(changes[0].name, "Slot");
+        assert_eq!(changes[0].last, Some(-5.0));
+        assert_eq!(changes[0].session, Some(5.0));
+        // Missing from the first refresh
+        assert_eq!(changes[1].last, Some(1.0));
+        assert_eq!(changes[1].session, None);
+    }
+
+    #[test]
+    fn stats_skip_missing_readings() {
+        let mut session = Session::default();
+        for (secs, metrics) in [
+            (0, vec![("Slot", 4.0)]),
+            (5, vec![("Slot", 1.0), ("Lag", 7.0)]),
+            (10, vec![("Slot", 7.0)]),
+        ] {
+            session.record(at(secs), Ok(metrics)).unwrap();
+        }
+        let slot = stats(&session.history, "Slot").unwrap();
+        assert_eq!((slot.min, slot.max, slot.avg), (1.0, 7.0, 4.0));
+        let lag = stats(&session.history, "Lag").unwrap();
+        assert_eq!((lag.min, lag.max, lag.avg), (7.0, 7.0, 7.0));
+        assert!(stats(&session.history, "Latency").is_none());
+    }
+}
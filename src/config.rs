@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -8,10 +9,16 @@ use std::path::PathBuf;
 /// Stores:
 /// - `cluster`: The name of the active cluster (e.g., "devnet", "mainnet").
 /// - `rpc_url`: The full HTTP/HTTPS URL for the RPC endpoint.
+/// - `watchlist`: Addresses aggregated by `portfolio` when none are given.
+/// - `labels`: User defined names for addresses, keyed by pubkey.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
     pub cluster: String,
     pub rpc_url: String,
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
 }
 
 impl Config {
@@ -20,6 +27,8 @@ impl Config {
         Self {
             cluster: "devnet".to_string(),
             rpc_url: "https://api.devnet.solana.com".to_string(),
+            watchlist: Vec::new(),
+            labels: BTreeMap::new(),
         }
     }
 
@@ -35,10 +44,10 @@ impl Config {
     /// Returns default config configuration if file is missing or invalid.
     pub fn load() -> Self {
         let path = Self::get_path();
-        if let Ok(data) = fs::read_to_string(path) {
-            if let Ok(config) = serde_json::from_str(&data) {
-                return config;
-            }
+        if let Ok(data) = fs::read_to_string(path)
+            && let Ok(config) = serde_json::from_str(&data)
+        {
+            return config;
         }
         Self::default()
     }
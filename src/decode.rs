@@ -0,0 +1,749 @@
+use crate::labels;
+use crate::utils;
+use anyhow::Result;
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::epoch_schedule::EpochSchedule;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::slot_history::{self, SlotHistory};
+use solana_sdk::stake::state::StakeStateV2;
+use solana_sdk::stake_history::StakeHistory;
+use solana_sdk::sysvar::{self, last_restart_slot::LastRestartSlot};
+use solana_sdk::{
+    address_lookup_table, bpf_loader_upgradeable, config::program as config_program, nonce, stake,
+    system_program, vote,
+};
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+use std::str::FromStr;
+
+/// Decoded account fields as `(name, value)` pairs, in display order.
+pub type Fields = Vec<(String, String)>;
+
+/// What an account holds, determined from its owner and data layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    Mint,
+    TokenAccount,
+    Stake,
+    Vote,
+    Program,
+    LookupTable,
+    Nonce,
+    Other,
+}
+
+impl AccountKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            AccountKind::Mint => "Token Mint",
+            AccountKind::TokenAccount => "Token Account",
+            AccountKind::Stake => "Stake Account",
+            AccountKind::Vote => "Vote Account",
+            AccountKind::Program => "Program",
+            AccountKind::LookupTable => "Address Lookup Table",
+            AccountKind::Nonce => "Nonce Account",
+            AccountKind::Other => "Account",
+        }
+    }
+}
+
+/// Whether `owner` is the SPL Token or Token-2022 program.
+pub fn is_token_program(owner: &Pubkey) -> bool {
+    *owner == spl_token_2022::id() || owner.to_string() == crate::commands::token::TOKEN_PROGRAM_ID
+}
+
+/// Classify an account by its owner program and data layout.
+pub fn kind(account: &Account) -> AccountKind {
+    let owner = &account.owner;
+    if is_token_program(owner) {
+        if StateWithExtensions::<Mint>::unpack(&account.data).is_ok() {
+            return AccountKind::Mint;
+        }
+        if StateWithExtensions::<TokenAccount>::unpack(&account.data).is_ok() {
+            return AccountKind::TokenAccount;
+        }
+    } else if *owner == stake::program::id() {
+        return AccountKind::Stake;
+    } else if *owner == vote::program::id() {
+        return AccountKind::Vote;
+    } else if *owner == address_lookup_table::program::id() {
+        return AccountKind::LookupTable;
+    } else if *owner == system_program::id() && account.data.len() == nonce::State::size() {
+        return AccountKind::Nonce;
+    }
+    if account.executable
+        || (*owner == bpf_loader_upgradeable::id()
+            && matches!(
+                bincode::deserialize(&account.data),
+                Ok(bpf_loader_upgradeable::UpgradeableLoaderState::Program { .. })
+            ))
+    {
+        return AccountKind::Program;
+    }
+    AccountKind::Other
+}
+
+/// Print decoded fields in the `name : value` layout used across commands.
+pub fn print_fields(fields: &Fields) {
+    for (name, value) in fields {
+        println!("{:<25} : {}", name, value);
+    }
+}
+
+/// Flatten a JSON value (such as a parsed instruction) into dotted fields.
+///
+/// Arrays of plain values are shown inline; strings that are pubkeys are
+/// shown with their labels.
+pub fn json_fields(value: &serde_json::Value) -> Fields {
+    let mut fields = Fields::new();
+    flatten_json(value, "", &mut fields);
+    fields
+}
+
+fn flatten_json(value: &serde_json::Value, path: &str, out: &mut Fields) {
+    use serde_json::Value;
+    let join = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (name, v) in map {
+                flatten_json(v, &join(name), out);
+            }
+        }
+        Value::Array(items) if items.iter().any(|v| v.is_object() || v.is_array()) => {
+            for (i, v) in items.iter().enumerate() {
+                flatten_json(v, &format!("{}[{}]", path, i), out);
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(json_scalar).collect();
+            out.push((path.to_string(), format!("[{}]", items.join(", "))));
+        }
+        other => out.push((path.to_string(), json_scalar(other))),
+    }
+}
+
+fn json_scalar(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) if s.len() >= 32 && Pubkey::from_str(s).is_ok() => {
+            labels::display(s)
+        }
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn field(fields: &mut Fields, name: &str, value: impl ToString) {
+    fields.push((name.to_string(), value.to_string()));
+}
+
+fn pubkey(key: &Pubkey) -> String {
+    labels::display(&key.to_string())
+}
+
+fn optional_pubkey(key: &COption<Pubkey>) -> String {
+    match key {
+        COption::Some(key) => pubkey(key),
+        COption::None => "None".to_string(),
+    }
+}
+
+/// Decode an SPL Token / Token-2022 mint.
+pub fn mint(data: &[u8]) -> Result<Fields> {
+    let state = StateWithExtensions::<Mint>::unpack(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode mint: {}", e))?;
+    let mint = state.base;
+    let mut fields = Fields::new();
+    field(
+        &mut fields,
+        "Supply",
+        utils::format_amount(mint.supply as i128, mint.decimals),
+    );
+    field(&mut fields, "Raw Supply", mint.supply);
+    field(&mut fields, "Decimals", mint.decimals);
+    field(
+        &mut fields,
+        "Mint Authority",
+        optional_pubkey(&mint.mint_authority),
+    );
+    field(
+        &mut fields,
+        "Freeze Authority",
+        optional_pubkey(&mint.freeze_authority),
+    );
+    field(&mut fields, "Initialized", mint.is_initialized);
+    push_extensions(&mut fields, state.get_extension_types());
+    Ok(fields)
+}
+
+/// Decode an SPL Token / Token-2022 token account.
+pub fn token_account(data: &[u8]) -> Result<Fields> {
+    let state = StateWithExtensions::<TokenAccount>::unpack(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode token account: {}", e))?;
+    let account = state.base;
+    let mut fields = Fields::new();
+    field(&mut fields, "Mint", pubkey(&account.mint));
+    field(&mut fields, "Owner", pubkey(&account.owner));
+    field(&mut fields, "Raw Amount", account.amount);
+    field(&mut fields, "State", format!("{:?}", account.state));
+    field(&mut fields, "Delegate", optional_pubkey(&account.delegate));
+    if account.delegate.is_some() {
+        field(&mut fields, "Delegated Amount", account.delegated_amount);
+    }
+    if let COption::Some(reserve) = account.is_native {
+        field(&mut fields, "Native (Rent Reserve)", reserve);
+    }
+    field(
+        &mut fields,
+        "Close Authority",
+        optional_pubkey(&account.close_authority),
+    );
+    push_extensions(&mut fields, state.get_extension_types());
+    Ok(fields)
+}
+
+fn push_extensions<E: std::fmt::Debug, Err>(fields: &mut Fields, types: Result<Vec<E>, Err>) {
+    if let Ok(types) = types
+        && !types.is_empty()
+    {
+        let names: Vec<String> = types.iter().map(|t| format!("{:?}", t)).collect();
+        field(fields, "Extensions", names.join(", "));
+    }
+}
+
+/// Decode a vote account.
+pub fn vote_account(data: &[u8]) -> Result<Fields> {
+    let state = vote::state::VoteState::deserialize(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode vote account: {}", e))?;
+    let mut fields = Fields::new();
+    field(
+        &mut fields,
+        "Validator Identity",
+        pubkey(&state.node_pubkey),
+    );
+    if let Some((epoch, voter)) = state.authorized_voters().last() {
+        field(
+            &mut fields,
+            "Authorized Voter",
+            format!("{} (since epoch {})", pubkey(voter), epoch),
+        );
+    }
+    field(
+        &mut fields,
+        "Authorized Withdrawer",
+        pubkey(&state.authorized_withdrawer),
+    );
+    field(&mut fields, "Commission", format!("{}%", state.commission));
+    field(
+        &mut fields,
+        "Root Slot",
+        state
+            .root_slot
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "None".to_string()),
+    );
+    field(
+        &mut fields,
+        "Last Vote",
+        state
+            .last_voted_slot()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "None".to_string()),
+    );
+    field(&mut fields, "Credits", state.credits());
+    if let Some((epoch, credits, prev)) = state.epoch_credits().last() {
+        field(
+            &mut fields,
+            "Current Epoch Credits",
+            format!("{} (epoch {})", credits.saturating_sub(*prev), epoch),
+        );
+    }
+    let ts = &state.last_timestamp;
+    field(
+        &mut fields,
+        "Last Timestamp",
+        format!(
+            "{} (slot {})",
+            chrono::DateTime::from_timestamp(ts.timestamp, 0)
+                .unwrap_or_default()
+                .format("%Y-%m-%d %H:%M:%S UTC"),
+            ts.slot
+        ),
+    );
+    Ok(fields)
+}
+
+/// Decode a durable nonce account.
+pub fn nonce_account(data: &[u8]) -> Result<Fields> {
+    let versions: nonce::state::Versions = bincode::deserialize(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode nonce account: {}", e))?;
+    let mut fields = Fields::new();
+    match versions.state() {
+        nonce::State::Uninitialized => field(&mut fields, "State", "Uninitialized"),
+        nonce::State::Initialized(data) => {
+            field(&mut fields, "State", "Initialized");
+            field(&mut fields, "Authority", pubkey(&data.authority));
+            field(&mut fields, "Durable Nonce", data.blockhash());
+            field(
+                &mut fields,
+                "Lamports per Signature",
+                data.fee_calculator.lamports_per_signature,
+            );
+        }
+    }
+    Ok(fields)
+}
+
+/// Decode account data based on its owner program (and address, for sysvars).
+///
+/// Returns the name of the layout with its fields, or `None` when the owner
+/// has no built-in decoder.
+pub fn decode(address: &Pubkey, account: &Account) -> Option<(&'static str, Result<Fields>)> {
+    let data = &account.data;
+    let owner = &account.owner;
+    let decoded = match kind(account) {
+        AccountKind::Mint => ("SPL Token Mint", mint(data)),
+        AccountKind::TokenAccount => ("SPL Token Account", token_account(data)),
+        AccountKind::Stake => ("Stake Account", stake_account(data)),
+        AccountKind::Vote => ("Vote Account", vote_account(data)),
+        AccountKind::LookupTable => ("Address Lookup Table", lookup_table(data)),
+        AccountKind::Nonce => ("Nonce Account", nonce_account(data)),
+        _ if *owner == bpf_loader_upgradeable::id() => {
+            ("Upgradeable Loader State", upgradeable_loader(data))
+        }
+        _ if *owner == sysvar::id() => ("Sysvar", sysvar(address, data, SYSVAR_PREVIEW)),
+        _ if *owner == config_program::id() => ("Config Account", config_account(data)),
+        _ => return None,
+    };
+    Some(decoded)
+}
+
+/// Decode a stake account.
+pub fn stake_account(data: &[u8]) -> Result<Fields> {
+    let state: StakeStateV2 = bincode::deserialize(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode stake account: {}", e))?;
+    let mut fields = Fields::new();
+    let (meta, stake) = match state {
+        StakeStateV2::Uninitialized => {
+            field(&mut fields, "State", "Uninitialized");
+            return Ok(fields);
+        }
+        StakeStateV2::RewardsPool => {
+            field(&mut fields, "State", "Rewards Pool");
+            return Ok(fields);
+        }
+        StakeStateV2::Initialized(meta) => {
+            field(&mut fields, "State", "Initialized (Undelegated)");
+            (meta, None)
+        }
+        StakeStateV2::Stake(meta, stake, _) => {
+            field(&mut fields, "State", "Delegated");
+            (meta, Some(stake))
+        }
+    };
+    field(
+        &mut fields,
+        "Rent Exempt Reserve",
+        format!(
+            "{} SOL",
+            utils::format_sol(meta.rent_exempt_reserve as i128)
+        ),
+    );
+    field(&mut fields, "Staker", pubkey(&meta.authorized.staker));
+    field(
+        &mut fields,
+        "Withdrawer",
+        pubkey(&meta.authorized.withdrawer),
+    );
+    let lockup = &meta.lockup;
+    if *lockup != Default::default() {
+        field(&mut fields, "Lockup Epoch", lockup.epoch);
+        field(&mut fields, "Lockup Timestamp", lockup.unix_timestamp);
+        field(&mut fields, "Lockup Custodian", pubkey(&lockup.custodian));
+    } else {
+        field(&mut fields, "Lockup", "None");
+    }
+    if let Some(stake) = stake {
+        let delegation = &stake.delegation;
+        field(
+            &mut fields,
+            "Delegated Vote Account",
+            pubkey(&delegation.voter_pubkey),
+        );
+        field(
+            &mut fields,
+            "Delegated Stake",
+            format!("{} SOL", utils::format_sol(delegation.stake as i128)),
+        );
+        field(
+            &mut fields,
+            "Activation Epoch",
+            epoch_or_none(delegation.activation_epoch),
+        );
+        field(
+            &mut fields,
+            "Deactivation Epoch",
+            epoch_or_none(delegation.deactivation_epoch),
+        );
+        field(&mut fields, "Credits Observed", stake.credits_observed);
+    }
+    Ok(fields)
+}
+
+fn epoch_or_none(epoch: u64) -> String {
+    if epoch == u64::MAX {
+        "None".to_string()
+    } else {
+        epoch.to_string()
+    }
+}
+
+/// Decode an address lookup table.
+pub fn lookup_table(data: &[u8]) -> Result<Fields> {
+    let table = address_lookup_table::state::AddressLookupTable::deserialize(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode lookup table: {}", e))?;
+    let meta = &table.meta;
+    let mut fields = Fields::new();
+    field(
+        &mut fields,
+        "Authority",
+        meta.authority
+            .map(|a| pubkey(&a))
+            .unwrap_or_else(|| "None (Frozen)".to_string()),
+    );
+    field(
+        &mut fields,
+        "Deactivation Slot",
+        epoch_or_none(meta.deactivation_slot),
+    );
+    field(&mut fields, "Last Extended Slot", meta.last_extended_slot);
+    field(
+        &mut fields,
+        "Last Extended Start Index",
+        meta.last_extended_slot_start_index,
+    );
+    field(&mut fields, "Addresses", table.addresses.len());
+    for (i, address) in table.addresses.iter().enumerate() {
+        field(&mut fields, &format!("  [{}]", i), pubkey(address));
+    }
+    Ok(fields)
+}
+
+/// Decode a BPF Upgradeable Loader account (program, program data or buffer).
+pub fn upgradeable_loader(data: &[u8]) -> Result<Fields> {
+    use bpf_loader_upgradeable::UpgradeableLoaderState;
+    let state: UpgradeableLoaderState = bincode::deserialize(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode loader state: {}", e))?;
+    let authority = |a: Option<Pubkey>| {
+        a.map(|a| pubkey(&a))
+            .unwrap_or_else(|| "None (Immutable)".to_string())
+    };
+    let mut fields = Fields::new();
+    match state {
+        UpgradeableLoaderState::Uninitialized => field(&mut fields, "Type", "Uninitialized"),
+        UpgradeableLoaderState::Buffer { authority_address } => {
+            field(&mut fields, "Type", "Buffer");
+            field(&mut fields, "Authority", authority(authority_address));
+            field(
+                &mut fields,
+                "Buffer Data Size",
+                format!(
+                    "{} bytes",
+                    data.len()
+                        .saturating_sub(UpgradeableLoaderState::size_of_buffer_metadata())
+                ),
+            );
+        }
+        UpgradeableLoaderState::Program {
+            programdata_address,
+        } => {
+            field(&mut fields, "Type", "Program");
+            field(&mut fields, "Program Data Account", programdata_address);
+        }
+        UpgradeableLoaderState::ProgramData {
+            slot,
+            upgrade_authority_address,
+        } => {
+            field(&mut fields, "Type", "Program Data");
+            field(&mut fields, "Last Deployed Slot", slot);
+            field(
+                &mut fields,
+                "Upgrade Authority",
+                authority(upgrade_authority_address),
+            );
+            field(
+                &mut fields,
+                "Program Size",
+                format!(
+                    "{} bytes",
+                    data.len()
+                        .saturating_sub(UpgradeableLoaderState::size_of_programdata_metadata())
+                ),
+            );
+        }
+    }
+    Ok(fields)
+}
+
+/// Decode a Config program account (e.g. published validator info).
+///
+/// The data starts with a short-vec of `(Pubkey, is_signer)` keys followed
+/// by the bincode encoded config value; validator info stores a JSON string.
+pub fn config_account(data: &[u8]) -> Result<Fields> {
+    let truncated = || anyhow::anyhow!("Config account data is truncated");
+    let (count, mut offset) = decode_short_vec_len(data).ok_or_else(truncated)?;
+    let mut fields = Fields::new();
+    let mut is_validator_info = false;
+    for i in 0..count {
+        let entry = data.get(offset..offset + 33).ok_or_else(truncated)?;
+        let key = Pubkey::try_from(&entry[..32]).map_err(|_| truncated())?;
+        is_validator_info |= key.to_string() == VALIDATOR_INFO_KEY;
+        let signer = if entry[32] != 0 { " (signer)" } else { "" };
+        field(
+            &mut fields,
+            &format!("Key [{}]", i),
+            format!("{}{}", pubkey(&key), signer),
+        );
+        offset += 33;
+    }
+
+    let rest = &data[offset..];
+    match bincode::deserialize::<String>(rest) {
+        Ok(text) if is_validator_info => {
+            field(&mut fields, "Type", "Validator Info");
+            match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&text) {
+                Ok(info) => {
+                    for (key, value) in info {
+                        let value = value
+                            .as_str()
+                            .map(str::to_string)
+                            .unwrap_or(value.to_string());
+                        field(&mut fields, &key, value);
+                    }
+                }
+                Err(_) => field(&mut fields, "Info", text),
+            }
+        }
+        Ok(text) => field(&mut fields, "Value", text),
+        Err(_) => field(&mut fields, "Config Data", format!("{} bytes", rest.len())),
+    }
+    Ok(fields)
+}
+
+const VALIDATOR_INFO_KEY: &str = "Va1idator1nfo111111111111111111111111111111";
+
+/// Decode a compact-u16 length prefix, returning the value and bytes consumed.
+fn decode_short_vec_len(data: &[u8]) -> Option<(usize, usize)> {
+    let mut value = 0usize;
+    for i in 0..3 {
+        let byte = *data.get(i)?;
+        value |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// Decode a sysvar account by its address.
+///
+/// List-valued sysvars show at most `limit` entries.
+#[allow(deprecated)] // RecentBlockhashes is deprecated but still readable
+pub fn sysvar(address: &Pubkey, data: &[u8], limit: usize) -> Result<Fields> {
+    let mut fields = Fields::new();
+    let err = |e: bincode::Error| anyhow::anyhow!("Failed to decode sysvar: {}", e);
+    if *address == sysvar::clock::id() {
+        let clock: Clock = bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Clock");
+        field(&mut fields, "Slot", clock.slot);
+        field(&mut fields, "Epoch", clock.epoch);
+        field(
+            &mut fields,
+            "Epoch Start Timestamp",
+            format_time(clock.epoch_start_timestamp),
+        );
+        field(
+            &mut fields,
+            "Leader Schedule Epoch",
+            clock.leader_schedule_epoch,
+        );
+        field(
+            &mut fields,
+            "Unix Timestamp",
+            format_time(clock.unix_timestamp),
+        );
+    } else if *address == sysvar::rent::id() {
+        let rent: Rent = bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Rent");
+        field(
+            &mut fields,
+            "Lamports per Byte-Year",
+            rent.lamports_per_byte_year,
+        );
+        field(
+            &mut fields,
+            "Exemption Threshold",
+            format!("{} years", rent.exemption_threshold),
+        );
+        field(
+            &mut fields,
+            "Burn Percent",
+            format!("{}%", rent.burn_percent),
+        );
+    } else if *address == sysvar::epoch_schedule::id() {
+        let schedule: EpochSchedule = bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Epoch Schedule");
+        field(&mut fields, "Slots per Epoch", schedule.slots_per_epoch);
+        field(
+            &mut fields,
+            "Leader Schedule Slot Offset",
+            schedule.leader_schedule_slot_offset,
+        );
+        field(&mut fields, "Warmup", schedule.warmup);
+        field(
+            &mut fields,
+            "First Normal Epoch",
+            schedule.first_normal_epoch,
+        );
+        field(&mut fields, "First Normal Slot", schedule.first_normal_slot);
+    } else if *address == sysvar::slot_hashes::id() {
+        let hashes: Vec<(u64, solana_sdk::hash::Hash)> = bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Slot Hashes");
+        field(&mut fields, "Entries", hashes.len());
+        for (slot, hash) in hashes.iter().take(limit) {
+            field(&mut fields, &format!("  Slot {}", slot), hash);
+        }
+        more(&mut fields, hashes.len(), limit);
+    } else if *address == sysvar::stake_history::id() {
+        let history: StakeHistory = bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Stake History");
+        field(&mut fields, "Entries", history.len());
+        for (epoch, entry) in history.iter().take(limit) {
+            field(
+                &mut fields,
+                &format!("  Epoch {}", epoch),
+                format!(
+                    "effective {} / activating {} / deactivating {} SOL",
+                    utils::format_sol(entry.effective as i128),
+                    utils::format_sol(entry.activating as i128),
+                    utils::format_sol(entry.deactivating as i128)
+                ),
+            );
+        }
+        more(&mut fields, history.len(), limit);
+    } else if *address == sysvar::recent_blockhashes::id() {
+        let entries: Vec<sysvar::recent_blockhashes::Entry> =
+            bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Recent Blockhashes (Deprecated)");
+        field(&mut fields, "Entries", entries.len());
+        for entry in entries.iter().take(limit) {
+            field(
+                &mut fields,
+                "  Blockhash",
+                format!(
+                    "{} ({} lamports/signature)",
+                    entry.blockhash, entry.fee_calculator.lamports_per_signature
+                ),
+            );
+        }
+        more(&mut fields, entries.len(), limit);
+    } else if *address == sysvar::epoch_rewards::id() {
+        field(&mut fields, "Sysvar", "Epoch Rewards");
+        fields.extend(epoch_rewards(data)?);
+    } else if *address == sysvar::last_restart_slot::id() {
+        let last: LastRestartSlot = bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Last Restart Slot");
+        field(&mut fields, "Last Restart Slot", last.last_restart_slot);
+    } else if *address == sysvar::slot_history::id() {
+        let history: SlotHistory = bincode::deserialize(data).map_err(err)?;
+        field(&mut fields, "Sysvar", "Slot History");
+        field(&mut fields, "Newest Slot", history.newest());
+        field(&mut fields, "Oldest Slot", history.oldest());
+        let produced = (history.oldest()..=history.newest())
+            .filter(|slot| history.check(*slot) == slot_history::Check::Found)
+            .count();
+        field(&mut fields, "Slots with Blocks", produced);
+    } else {
+        field(&mut fields, "Sysvar", labels::display(&address.to_string()));
+        field(
+            &mut fields,
+            "Data",
+            format!("{} bytes (no decoder)", data.len()),
+        );
+    }
+    Ok(fields)
+}
+
+/// Number of entries shown for list-valued sysvars.
+pub const SYSVAR_PREVIEW: usize = 10;
+
+/// Note how many list entries were left out.
+fn more(fields: &mut Fields, total: usize, shown: usize) {
+    if total > shown {
+        field(fields, "  …", format!("{} more", total - shown));
+    }
+}
+
+/// Decode the EpochRewards sysvar.
+///
+/// Clusters with partitioned epoch rewards use an 81 byte layout
+/// (distribution start height, partitions, parent blockhash, total points,
+/// totals and an active flag); older clusters use the 24 byte layout.
+pub fn epoch_rewards(data: &[u8]) -> Result<Fields> {
+    let u64_at = |offset: usize| -> Result<u64> {
+        data.get(offset..offset + 8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+            .ok_or_else(|| anyhow::anyhow!("EpochRewards data is truncated"))
+    };
+    let mut fields = Fields::new();
+    if data.len() >= 81 {
+        let parent_blockhash = solana_sdk::hash::Hash::new(&data[16..48]);
+        let total_points = u128::from_le_bytes(data[48..64].try_into().unwrap());
+        let total = u64_at(64)?;
+        let distributed = u64_at(72)?;
+        field(&mut fields, "Active", data[80] != 0);
+        field(&mut fields, "Distribution Start Height", u64_at(0)?);
+        field(&mut fields, "Partitions", u64_at(8)?);
+        field(&mut fields, "Parent Blockhash", parent_blockhash);
+        field(&mut fields, "Total Points", total_points);
+        push_reward_totals(&mut fields, total, distributed);
+    } else {
+        let total = u64_at(0)?;
+        let distributed = u64_at(8)?;
+        push_reward_totals(&mut fields, total, distributed);
+        field(&mut fields, "Distribution Complete Height", u64_at(16)?);
+    }
+    Ok(fields)
+}
+
+fn push_reward_totals(fields: &mut Fields, total: u64, distributed: u64) {
+    field(
+        fields,
+        "Total Rewards",
+        format!("{} SOL", utils::format_sol(total as i128)),
+    );
+    field(
+        fields,
+        "Distributed Rewards",
+        format!("{} SOL", utils::format_sol(distributed as i128)),
+    );
+    if total > 0 {
+        field(
+            fields,
+            "Distribution Progress",
+            format!("{:.2}%", distributed as f64 / total as f64 * 100.0),
+        );
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    format!("{} ({})", utils::format_utc(timestamp), timestamp)
+}
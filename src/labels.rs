@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+/// Well-known programs, sysvars and mints shown by name.
+const KNOWN: &[(&str, &str)] = &[
+    // Native programs
+    ("11111111111111111111111111111111", "System Program"),
+    (
+        "Stake11111111111111111111111111111111111111",
+        "Stake Program",
+    ),
+    (
+        "Vote111111111111111111111111111111111111111",
+        "Vote Program",
+    ),
+    (
+        "Config1111111111111111111111111111111111111",
+        "Config Program",
+    ),
+    (
+        "ComputeBudget111111111111111111111111111111",
+        "Compute Budget Program",
+    ),
+    (
+        "AddressLookupTab1e1111111111111111111111111",
+        "Address Lookup Table Program",
+    ),
+    (
+        "BPFLoader1111111111111111111111111111111111",
+        "BPF Loader (Deprecated)",
+    ),
+    (
+        "BPFLoader2111111111111111111111111111111111",
+        "BPF Loader 2",
+    ),
+    (
+        "BPFLoaderUpgradeab1e11111111111111111111111",
+        "BPF Upgradeable Loader",
+    ),
+    ("LoaderV411111111111111111111111111111111111", "Loader V4"),
+    (
+        "NativeLoader1111111111111111111111111111111",
+        "Native Loader",
+    ),
+    (
+        "Ed25519SigVerify111111111111111111111111111",
+        "Ed25519 SigVerify Program",
+    ),
+    (
+        "KeccakSecp256k11111111111111111111111111111",
+        "Secp256k1 SigVerify Program",
+    ),
+    (
+        "ZkTokenProof1111111111111111111111111111111",
+        "ZK Token Proof Program",
+    ),
+    // SPL programs
+    (
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        "Token Program",
+    ),
+    (
+        "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+        "Token-2022 Program",
+    ),
+    (
+        "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+        "Associated Token Program",
+    ),
+    (
+        "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+        "Memo Program",
+    ),
+    (
+        "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo",
+        "Memo Program (v1)",
+    ),
+    (
+        "SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy",
+        "Stake Pool Program",
+    ),
+    (
+        "namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX",
+        "Name Service Program",
+    ),
+    // Ecosystem programs
+    (
+        "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+        "Token Metadata Program",
+    ),
+    (
+        "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+        "Jupiter Aggregator v6",
+    ),
+    (
+        "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB",
+        "Jupiter Aggregator v4",
+    ),
+    (
+        "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+        "Orca Whirlpools",
+    ),
+    (
+        "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+        "Raydium AMM v4",
+    ),
+    (
+        "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK",
+        "Raydium CLMM",
+    ),
+    (
+        "srmqPvymJeFKQ4zGQed1GFppgkRHB9kaLCEdLhqm8Z1",
+        "OpenBook (Serum v3)",
+    ),
+    (
+        "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
+        "Meteora DLMM",
+    ),
+    (
+        "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD",
+        "Marinade Finance",
+    ),
+    // Sysvars
+    (
+        "SysvarC1ock11111111111111111111111111111111",
+        "Clock Sysvar",
+    ),
+    ("SysvarRent111111111111111111111111111111111", "Rent Sysvar"),
+    (
+        "SysvarEpochSchedu1e111111111111111111111111",
+        "Epoch Schedule Sysvar",
+    ),
+    ("SysvarFees111111111111111111111111111111111", "Fees Sysvar"),
+    (
+        "Sysvar1nstructions1111111111111111111111111",
+        "Instructions Sysvar",
+    ),
+    (
+        "SysvarRecentB1ockHashes11111111111111111111",
+        "Recent Blockhashes Sysvar",
+    ),
+    (
+        "SysvarS1otHashes111111111111111111111111111",
+        "Slot Hashes Sysvar",
+    ),
+    (
+        "SysvarS1otHistory11111111111111111111111111",
+        "Slot History Sysvar",
+    ),
+    (
+        "SysvarStakeHistory1111111111111111111111111",
+        "Stake History Sysvar",
+    ),
+    (
+        "SysvarEpochRewards1111111111111111111111111",
+        "Epoch Rewards Sysvar",
+    ),
+    (
+        "SysvarLastRestartS1ot1111111111111111111111",
+        "Last Restart Slot Sysvar",
+    ),
+    // Mints
+    ("So11111111111111111111111111111111111111112", "Wrapped SOL"),
+    ("EPjFWdd5AufqSSqeM2qN1xyybapC8G4wEGGkZwyTDt1v", "USDC"),
+    ("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", "USDT"),
+];
+
+/// Labels applied when printing addresses.
+struct Labels {
+    enabled: bool,
+    user: BTreeMap<String, String>,
+}
+
+static LABELS: OnceLock<Labels> = OnceLock::new();
+
+/// Install the user's labels for this run.
+///
+/// Must be called once at startup; until then (or with `enabled` false)
+/// addresses are printed as-is.
+pub fn init(user: BTreeMap<String, String>, enabled: bool) {
+    let _ = LABELS.set(Labels { enabled, user });
+}
+
+/// Name of a well-known address from the built-in registry.
+pub fn builtin(address: &str) -> Option<&'static str> {
+    KNOWN
+        .iter()
+        .find(|(key, _)| *key == address)
+        .map(|(_, name)| *name)
+}
+
+/// All built-in labels as `(address, name)` pairs.
+pub fn builtins() -> &'static [(&'static str, &'static str)] {
+    KNOWN
+}
+
+/// Label for an address, preferring user labels over the built-in registry.
+pub fn lookup(address: &str) -> Option<&str> {
+    let labels = LABELS.get().filter(|l| l.enabled)?;
+    labels
+        .user
+        .get(address)
+        .map(String::as_str)
+        .or_else(|| builtin(address))
+}
+
+/// Render an address for display, e.g. `Token Program (Tokenkeg…)`.
+///
+/// Unlabelled addresses, and all addresses under `--no-labels`, are
+/// returned unchanged.
+pub fn display(address: &str) -> String {
+    match lookup(address) {
+        Some(name) => {
+            let short: String = address.chars().take(8).collect();
+            format!("{} ({}…)", name, short)
+        }
+        None => address.to_string(),
+    }
+}
@@ -1,6 +1,10 @@
+mod anchor;
 mod commands;
 mod config;
+mod decode;
+mod labels;
 mod rpc;
+mod utils;
 
 use clap::{Parser, Subcommand};
 use colored::*;
@@ -10,17 +14,26 @@ use config::Config;
 #[derive(Parser)]
 #[command(name = "raushan")]
 #[command(about = "Ultimate Solana Terminal Explorer", long_about = None)]
-#[command(version)]
+#[command(version, disable_version_flag = true)]
 struct Cli {
     #[arg(short = 'v', long = "version", action = clap::ArgAction::Version)]
     version: Option<bool>,
 
+    /// Print raw addresses instead of known program and user labels.
+    #[arg(long = "no-labels", global = true)]
+    no_labels: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    // --- INSPECT ---
+    /// Auto-detect a signature, slot, address or explorer URL and show the matching view.
+    #[command(name = "inspect")]
+    Inspect { input: String },
+
     // --- CLUSTER ---
     /// Set the active cluster (devnet, testnet, mainnet-beta).
     #[command(name = "cluster-set")]
@@ -36,7 +49,22 @@ enum Commands {
 
     /// Check the health status of the current cluster.
     #[command(name = "cluster-health")]
-    ClusterHealth,
+    ClusterHealth {
+        /// Compare slot, block height and ledger range against other endpoints.
+        /// Exits with 2 when lagging and 3 when unhealthy or unreachable; with
+        /// --watch the verdict is shown on every refresh and no exit code is set.
+        #[arg(long)]
+        deep: bool,
+        /// Endpoint (cluster name or URL) to compare against; repeatable.
+        /// Defaults to the public endpoint of the cluster with the same genesis hash.
+        #[arg(long, requires = "deep")]
+        against: Vec<String>,
+        /// Slots behind the best endpoint tolerated before reporting lag.
+        #[arg(long, default_value_t = 150, requires = "deep")]
+        max_lag: u64,
+        #[command(flatten)]
+        watch: commands::watch::WatchArgs,
+    },
 
     /// Display the Genesis Hash of the current cluster.
     #[command(name = "cluster-genesis")]
@@ -46,18 +74,49 @@ enum Commands {
     #[command(name = "cluster-nodes")]
     ClusterNodes,
 
+    /// Benchmark RPC latency, errors and throttling per method across endpoints.
+    #[command(name = "rpc-bench")]
+    RpcBench {
+        #[command(flatten)]
+        args: commands::bench::BenchArgs,
+    },
+
+    /// Activation status of every known feature gate (optionally diffed against another cluster).
+    #[command(name = "cluster-features")]
+    ClusterFeatures {
+        #[command(flatten)]
+        args: commands::features::FeatureArgs,
+    },
+
+    /// Nodes and stake by software version, feature set and client.
+    #[command(name = "cluster-versions")]
+    ClusterVersions {
+        /// Flag stake running versions older than this (e.g. 2.0.15).
+        #[arg(long)]
+        min_version: Option<String>,
+    },
+
     // --- NETWORK ---
     /// Detailed dashboard of the current network status (Epoch, Slot, Height).
     #[command(name = "network-status")]
-    NetworkStatus,
+    NetworkStatus {
+        #[command(flatten)]
+        watch: commands::watch::WatchArgs,
+    },
 
     /// Real-time Transactions Per Second (TPS) tracker with performance overview.
     #[command(name = "network-tps")]
-    NetworkTps,
+    NetworkTps {
+        #[command(flatten)]
+        watch: commands::watch::WatchArgs,
+    },
 
     /// Show total, circulating, and non-circulating SOL supply.
     #[command(name = "network-supply")]
-    NetworkSupply,
+    NetworkSupply {
+        #[command(flatten)]
+        watch: commands::watch::WatchArgs,
+    },
 
     /// Display current inflation rates (Total, Validator, Foundation).
     #[command(name = "network-inflation")]
@@ -67,10 +126,66 @@ enum Commands {
     #[command(name = "network-epoch-info")]
     NetworkEpochInfo,
 
+    /// Show when a slot was produced (or estimate a future slot's time).
+    #[command(name = "slot-time")]
+    SlotTime { slot: u64 },
+
+    /// Find the slot produced at a time (YYYY-MM-DD, RFC3339 or unix timestamp).
+    #[command(name = "time-slot")]
+    TimeSlot { timestamp: String },
+
+    /// Upcoming leaders with timing estimates and leader-slot share per validator.
+    #[command(name = "leader-schedule")]
+    LeaderSchedule {
+        /// Number of upcoming leader rotations to show.
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+        /// List every leader slot of this validator identity in the epoch.
+        #[arg(long)]
+        validator: Option<String>,
+    },
+
+    /// Read and decode a sysvar account (lists sysvars when no name is given).
+    #[command(name = "sysvar")]
+    Sysvar {
+        name: Option<commands::sysvar::SysvarName>,
+        /// Entries shown for list-valued sysvars (0 for all).
+        #[arg(long, default_value_t = decode::SYSVAR_PREVIEW)]
+        limit: usize,
+    },
+
+    /// Rent-exempt minimum balance for an account of the given data size.
+    #[command(name = "rent")]
+    Rent {
+        /// Account data size, up to the 10 MiB account limit.
+        #[arg(value_parser = clap::value_parser!(u64).range(..=solana_sdk::system_instruction::MAX_PERMITTED_DATA_LENGTH))]
+        bytes: u64,
+    },
+
     // --- ACCOUNT ---
     /// Fetch and display detailed account information and balance.
     #[command(name = "account-info")]
-    AccountInfo { address: String },
+    AccountInfo {
+        address: String,
+        #[command(flatten)]
+        dump: commands::account::DataArgs,
+    },
+
+    /// Stream changes to an account with a diff of its decoded fields.
+    #[command(name = "account-watch")]
+    AccountWatch {
+        address: String,
+        /// Append each change to this file as newline-delimited JSON.
+        #[arg(long, short = 'o')]
+        out: Option<std::path::PathBuf>,
+        /// Stop after this many changes.
+        #[arg(long)]
+        count: Option<usize>,
+    },
+
+    /// Check whether an account is rent exempt and what closing it would reclaim.
+    #[command(name = "rent-audit")]
+    RentAudit { address: String },
 
     /// Show SPL Token accounts owned by this address.
     #[command(name = "account-tokens")]
@@ -80,9 +195,62 @@ enum Commands {
     #[command(name = "account-stake")]
     AccountStake { address: String },
 
-    /// List recent transaction history for the account.
+    /// Decode a durable nonce account (Authority, Nonce, Fee).
+    #[command(name = "nonce-account")]
+    NonceAccount { address: String },
+
+    /// List transaction history for the account (paged, with date/status filters).
     #[command(name = "account-history")]
-    AccountHistory { address: String },
+    AccountHistory {
+        address: String,
+        #[command(flatten)]
+        filter: commands::account::HistoryArgs,
+    },
+
+    /// Export every SOL and token balance change of an address to CSV (resumable).
+    #[command(name = "account-export")]
+    AccountExport {
+        address: String,
+        /// Only export transactions at or after this date (YYYY-MM-DD or RFC3339).
+        #[arg(long)]
+        from: Option<String>,
+        /// Only export transactions at or before this date (YYYY-MM-DD or RFC3339).
+        #[arg(long)]
+        to: Option<String>,
+        /// Output CSV file (defaults to <ADDRESS>.csv). Existing rows are kept and skipped.
+        #[arg(long, short)]
+        out: Option<std::path::PathBuf>,
+    },
+
+    // --- PORTFOLIO ---
+    /// Aggregate SOL, stake and token balances across wallets (or the watchlist).
+    #[command(name = "portfolio")]
+    Portfolio { addresses: Vec<String> },
+
+    /// Add an address to the saved portfolio watchlist.
+    #[command(name = "watchlist-add")]
+    WatchlistAdd { address: String },
+
+    /// Remove an address from the saved portfolio watchlist.
+    #[command(name = "watchlist-remove")]
+    WatchlistRemove { address: String },
+
+    /// Show the saved portfolio watchlist.
+    #[command(name = "watchlist-list")]
+    WatchlistList,
+
+    // --- LABELS ---
+    /// Attach a name to an address, shown wherever it is printed.
+    #[command(name = "label-add")]
+    LabelAdd { address: String, name: String },
+
+    /// Remove a user defined address label.
+    #[command(name = "label-remove")]
+    LabelRemove { address: String },
+
+    /// List user defined and built-in address labels.
+    #[command(name = "label-list")]
+    LabelList,
 
     // --- TRANSACTION ---
     /// Breakdown of a transaction (Status, Fee, Logs, Instructions).
@@ -93,6 +261,45 @@ enum Commands {
     #[command(name = "tx-logs")]
     TxLogs { sig: String },
 
+    /// Stream transaction logs in real time.
+    #[command(name = "logs-watch")]
+    LogsWatch {
+        /// Only transactions mentioning this address.
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        mentions: Option<String>,
+        /// All transactions except votes.
+        #[arg(long)]
+        all: bool,
+        /// Only show failed transactions.
+        #[arg(long)]
+        errors: bool,
+        /// Stop after this many transactions.
+        #[arg(long)]
+        count: Option<usize>,
+    },
+
+    /// Decode a serialized (unsigned or signed) transaction offline.
+    #[command(name = "tx-decode")]
+    TxDecode {
+        /// Base64 or base58 transaction; read from stdin when omitted or "-".
+        tx: Option<String>,
+        /// Read the transaction from a file instead.
+        #[arg(long, conflicts_with = "tx")]
+        file: Option<std::path::PathBuf>,
+        /// Input encoding; detected automatically by default.
+        #[arg(long, value_enum)]
+        encoding: Option<commands::transaction::TxEncoding>,
+    },
+
+    /// Follow a signature through processed, confirmed and finalized.
+    #[command(name = "tx-watch")]
+    TxWatch {
+        sig: String,
+        /// Recent blockhash of the transaction, to stop as soon as it expires.
+        #[arg(long)]
+        blockhash: Option<String>,
+    },
+
     /// Simulate a transaction (Dry run) - *Not fully implemented yet*.
     #[command(name = "tx-simulate")]
     TxSimulate { sig: String },
@@ -101,6 +308,10 @@ enum Commands {
     #[command(name = "tx-priority")]
     TxPriority,
 
+    /// Decode an Address Lookup Table (Authority, Slots, Addresses).
+    #[command(name = "alt-info")]
+    AltInfo { address: String },
+
     // --- BLOCK ---
     /// Deep dive into a specific block by slot number.
     #[command(name = "block-info")]
@@ -114,20 +325,52 @@ enum Commands {
     #[command(name = "block-rewards")]
     BlockRewards { slot: u64 },
 
+    /// Stream new confirmed blocks with leader, transaction counts, CU and fees.
+    #[command(name = "block-follow")]
+    BlockFollow {
+        /// Only list transactions that reference this program.
+        #[arg(long)]
+        program: Option<String>,
+        /// Poll getBlocks instead of subscribing to slots over websocket.
+        #[arg(long)]
+        poll: bool,
+        /// Stop after this many blocks.
+        #[arg(long)]
+        count: Option<usize>,
+    },
+
     // --- VALIDATOR ---
-    /// List top active validators by stake.
+    /// List validators with stake share, credits and the Nakamoto coefficient.
     #[command(name = "validator-list")]
-    ValidatorList,
+    ValidatorList {
+        #[command(flatten)]
+        args: commands::validator::ValidatorArgs,
+    },
 
     /// Get detailed info about a specific validator identity.
     #[command(name = "validator-info")]
     ValidatorInfo { identity: String },
 
+    /// Decode a vote account (Identity, Authorities, Commission, Credits).
+    #[command(name = "vote-account")]
+    VoteAccount { address: String },
+
+    /// Leader slots, blocks produced and skip rate per validator.
+    #[command(name = "block-production")]
+    BlockProduction {
+        #[command(flatten)]
+        args: commands::validator::ProductionArgs,
+    },
+
     // --- TOKEN ---
     /// Get details of a Token Mint (Supply, Decimals, Authorities).
     #[command(name = "token-mint")]
     TokenMint { address: String },
 
+    /// Get details of a token account (Mint, Owner, Balance, Delegate).
+    #[command(name = "token-account")]
+    TokenAccount { address: String },
+
     /// List largest holders of a specific Token Mint.
     #[command(name = "token-holders")]
     TokenHolders { address: String },
@@ -136,6 +379,19 @@ enum Commands {
     /// Inspect a specific stake account state and activation.
     #[command(name = "stake-account-info")]
     StakeAccountInfo { address: String },
+
+    /// Inflation rewards per epoch for stake or vote accounts.
+    #[command(name = "rewards")]
+    Rewards {
+        #[arg(required = true)]
+        addresses: Vec<String>,
+        /// Number of past epochs to fetch.
+        #[arg(long, default_value_t = 10)]
+        epochs: u64,
+        /// Also write the rewards to this CSV file.
+        #[arg(long, short)]
+        out: Option<std::path::PathBuf>,
+    },
     // --- PROGRAM ---
     /// details about a specific program (Owner, Data Size, Executable).
     #[command(name = "program-info")]
@@ -157,24 +413,68 @@ async fn main() {
 async fn run() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let mut cfg = Config::load();
+    labels::init(cfg.labels.clone(), !cli.no_labels);
 
     if let Some(cmd) = cli.command {
         match cmd {
+            Commands::Inspect { input } => {
+                commands::inspect::inspect(&rpc::get_client(&cfg.rpc_url), &input)?
+            }
+
             // Cluster
             Commands::ClusterSet { name } => commands::cluster::set(&mut cfg, name)?,
             Commands::ClusterGet => commands::cluster::get(&cfg)?,
             Commands::ClusterInfo => commands::cluster::info(&rpc::get_client(&cfg.rpc_url))?,
-            Commands::ClusterHealth => commands::cluster::health(&rpc::get_client(&cfg.rpc_url))?,
+            Commands::ClusterHealth {
+                deep: false, watch, ..
+            } => {
+                let client = rpc::get_client(&cfg.rpc_url);
+                commands::watch::run(&watch, || commands::cluster::health(&client))?
+            }
+            Commands::ClusterHealth {
+                deep: true,
+                mut against,
+                max_lag,
+                watch,
+            } => {
+                if against.is_empty() {
+                    let client = rpc::get_client(&cfg.rpc_url);
+                    against.push(commands::cluster::reference_url(&client)?);
+                }
+                // Only a single check sets the exit code; watching runs until interrupted
+                let mut code = 0;
+                commands::watch::run(&watch, || {
+                    let (status, metrics) =
+                        commands::cluster::deep_health(&cfg.rpc_url, &against, max_lag)?;
+                    code = status;
+                    Ok(metrics)
+                })?;
+                if code != 0 {
+                    std::process::exit(code);
+                }
+            }
             Commands::ClusterGenesis => commands::cluster::genesis(&rpc::get_client(&cfg.rpc_url))?,
             Commands::ClusterNodes => commands::cluster::get_nodes(&rpc::get_client(&cfg.rpc_url))?,
+            Commands::RpcBench { args } => commands::bench::run(&cfg.rpc_url, &args)?,
+            Commands::ClusterFeatures { args } => {
+                commands::features::list(&rpc::get_client(&cfg.rpc_url), &args)?
+            }
+            Commands::ClusterVersions { min_version } => {
+                commands::cluster::versions(&rpc::get_client(&cfg.rpc_url), min_version.as_deref())?
+            }
 
             // Network
-            Commands::NetworkStatus => {
-                commands::network::get_status(&rpc::get_client(&cfg.rpc_url))?
+            Commands::NetworkStatus { watch } => {
+                let client = rpc::get_client(&cfg.rpc_url);
+                commands::watch::run(&watch, || commands::network::get_status(&client))?
+            }
+            Commands::NetworkTps { watch } => {
+                let client = rpc::get_client(&cfg.rpc_url);
+                commands::watch::run(&watch, || commands::network::get_tps(&client))?
             }
-            Commands::NetworkTps => commands::network::get_tps(&rpc::get_client(&cfg.rpc_url))?,
-            Commands::NetworkSupply => {
-                commands::network::get_supply(&rpc::get_client(&cfg.rpc_url))?
+            Commands::NetworkSupply { watch } => {
+                let client = rpc::get_client(&cfg.rpc_url);
+                commands::watch::run(&watch, || commands::network::get_supply(&client))?
             }
             Commands::NetworkInflation => {
                 commands::network::get_inflation(&rpc::get_client(&cfg.rpc_url))?
@@ -182,10 +482,37 @@ async fn run() -> anyhow::Result<()> {
             Commands::NetworkEpochInfo => {
                 commands::network::get_epoch_schedule(&rpc::get_client(&cfg.rpc_url))?
             }
+            Commands::SlotTime { slot } => {
+                commands::network::slot_time(&rpc::get_client(&cfg.rpc_url), slot)?
+            }
+            Commands::TimeSlot { timestamp } => {
+                commands::network::time_slot(&rpc::get_client(&cfg.rpc_url), &timestamp)?
+            }
+            Commands::LeaderSchedule { count, validator } => {
+                commands::leader::show(&rpc::get_client(&cfg.rpc_url), count, validator.as_deref())?
+            }
+            Commands::Rent { bytes } => {
+                commands::rent::calculate(&rpc::get_client(&cfg.rpc_url), bytes as usize)?
+            }
+            Commands::Sysvar { name, limit } => {
+                let client = rpc::get_client(&cfg.rpc_url);
+                match name {
+                    Some(name) => commands::sysvar::show(&client, name, limit)?,
+                    None => commands::sysvar::list(&client)?,
+                }
+            }
 
             // Account
-            Commands::AccountInfo { address } => {
-                commands::account::get_info(&rpc::get_client(&cfg.rpc_url), &address)?
+            Commands::AccountInfo { address, dump } => {
+                commands::account::get_info(&rpc::get_client(&cfg.rpc_url), &address, &dump)?
+            }
+            Commands::AccountWatch {
+                address,
+                out,
+                count,
+            } => commands::account_watch::watch(&cfg.rpc_url, &address, out.as_deref(), count)?,
+            Commands::RentAudit { address } => {
+                commands::rent::audit(&rpc::get_client(&cfg.rpc_url), &address)?
             }
             Commands::AccountTokens { address } => {
                 commands::account::get_tokens(&rpc::get_client(&cfg.rpc_url), &address)?
@@ -193,10 +520,45 @@ async fn run() -> anyhow::Result<()> {
             Commands::AccountStake { address } => {
                 commands::account::get_stake(&rpc::get_client(&cfg.rpc_url), &address)?
             }
-            Commands::AccountHistory { address } => {
-                commands::account::get_history(&rpc::get_client(&cfg.rpc_url), &address)?
+            Commands::NonceAccount { address } => {
+                commands::account::get_nonce(&rpc::get_client(&cfg.rpc_url), &address)?
+            }
+            Commands::AccountHistory { address, filter } => {
+                commands::account::get_history(&rpc::get_client(&cfg.rpc_url), &address, &filter)?
+            }
+            Commands::AccountExport {
+                address,
+                from,
+                to,
+                out,
+            } => {
+                let out = out.unwrap_or_else(|| format!("{}.csv", address).into());
+                commands::export::export(
+                    &rpc::get_client(&cfg.rpc_url),
+                    &address,
+                    from.as_deref(),
+                    to.as_deref(),
+                    &out,
+                )?
             }
 
+            // Portfolio
+            Commands::Portfolio { addresses } => {
+                commands::portfolio::show(&rpc::get_client(&cfg.rpc_url), &cfg, &addresses)?
+            }
+            Commands::WatchlistAdd { address } => {
+                commands::portfolio::watchlist_add(&mut cfg, address)?
+            }
+            Commands::WatchlistRemove { address } => {
+                commands::portfolio::watchlist_remove(&mut cfg, address)?
+            }
+            Commands::WatchlistList => commands::portfolio::watchlist_list(&cfg)?,
+
+            // Labels
+            Commands::LabelAdd { address, name } => commands::label::add(&mut cfg, address, name)?,
+            Commands::LabelRemove { address } => commands::label::remove(&mut cfg, address)?,
+            Commands::LabelList => commands::label::list(&cfg)?,
+
             // Transaction
             Commands::TxInfo { sig } => {
                 commands::transaction::get_details(&rpc::get_client(&cfg.rpc_url), &sig)?
@@ -204,8 +566,28 @@ async fn run() -> anyhow::Result<()> {
             Commands::TxLogs { sig } => {
                 commands::transaction::get_logs(&rpc::get_client(&cfg.rpc_url), &sig)?
             }
+            Commands::LogsWatch {
+                mentions,
+                all,
+                errors,
+                count,
+            } => commands::logs::watch(
+                &cfg.rpc_url,
+                mentions.as_deref().filter(|_| !all),
+                errors,
+                count,
+            )?,
+            Commands::TxDecode { tx, file, encoding } => {
+                commands::transaction::decode(tx.as_deref(), file.as_deref(), encoding)?
+            }
+            Commands::TxWatch { sig, blockhash } => {
+                commands::tx_watch::watch(&cfg.rpc_url, &sig, blockhash.as_deref())?
+            }
             Commands::TxSimulate { sig: _ } => println!("Simulation feature coming soon."),
             Commands::TxPriority => println!("Priority fees feature coming soon."),
+            Commands::AltInfo { address } => {
+                commands::lookup_table::get_info(&rpc::get_client(&cfg.rpc_url), &address)?
+            }
 
             // Block
             Commands::BlockInfo { slot } => {
@@ -217,19 +599,33 @@ async fn run() -> anyhow::Result<()> {
             Commands::BlockRewards { slot } => {
                 commands::block::get_block(&rpc::get_client(&cfg.rpc_url), slot)?
             } // Currently returning everything
+            Commands::BlockFollow {
+                program,
+                poll,
+                count,
+            } => commands::block::follow(&cfg.rpc_url, program.as_deref(), poll, count)?,
 
             // Validator
-            Commands::ValidatorList => {
-                commands::validator::get_validators(&rpc::get_client(&cfg.rpc_url))?
+            Commands::ValidatorList { args } => {
+                commands::validator::get_validators(&rpc::get_client(&cfg.rpc_url), &args)?
             }
             Commands::ValidatorInfo { identity: _ } => {
                 println!("Validator detailed info coming soon.")
             }
+            Commands::VoteAccount { address } => {
+                commands::validator::get_vote_account(&rpc::get_client(&cfg.rpc_url), &address)?
+            }
+            Commands::BlockProduction { args } => {
+                commands::validator::get_block_production(&rpc::get_client(&cfg.rpc_url), &args)?
+            }
 
             // Token
             Commands::TokenMint { address } => {
                 commands::token::get_mint_info(&rpc::get_client(&cfg.rpc_url), &address)?
             }
+            Commands::TokenAccount { address } => {
+                commands::token::get_token_account(&rpc::get_client(&cfg.rpc_url), &address)?
+            }
             Commands::TokenHolders { address } => {
                 commands::token::get_holders(&rpc::get_client(&cfg.rpc_url), &address)?
             }
@@ -238,6 +634,16 @@ async fn run() -> anyhow::Result<()> {
             Commands::StakeAccountInfo { address } => {
                 commands::stake::get_stake_account(&rpc::get_client(&cfg.rpc_url), &address)?
             }
+            Commands::Rewards {
+                addresses,
+                epochs,
+                out,
+            } => commands::rewards::show(
+                &rpc::get_client(&cfg.rpc_url),
+                &addresses,
+                epochs,
+                out.as_deref(),
+            )?,
 
             // Program
             Commands::ProgramInfo { address } => {
@@ -1,3 +1,4 @@
+use solana_client::pubsub_client::PubsubClientSubscription;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 
@@ -5,3 +6,36 @@ pub fn get_client(rpc_url: &str) -> RpcClient {
     // Finalized commitment to give secure data
     RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::finalized())
 }
+
+/// WebSocket (pubsub) URL for an RPC URL.
+///
+/// Switches http to ws and https to wss. A local validator serves pubsub
+/// on the port after its RPC port, so 8899 becomes 8900.
+pub fn ws_url(rpc_url: &str) -> String {
+    let Ok(mut url) = reqwest::Url::parse(rpc_url) else {
+        return rpc_url.to_string();
+    };
+    let scheme = match url.scheme() {
+        "https" => "wss",
+        "http" => "ws",
+        other => other,
+    }
+    .to_string();
+    let _ = url.set_scheme(&scheme);
+    if url.port() == Some(8899) {
+        let _ = url.set_port(Some(8900));
+    }
+    url.to_string()
+}
+
+/// Drop a pubsub subscription without blocking the caller.
+///
+/// The blocking client unsubscribes on drop, which waits until the server
+/// sends another message; that may never happen, so it runs on a detached
+/// thread instead.
+pub fn close_subscription<T>(subscription: PubsubClientSubscription<T>)
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    std::thread::spawn(move || drop(subscription));
+}
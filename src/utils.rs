@@ -0,0 +1,113 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate};
+
+/// Parse a user supplied date into a unix timestamp.
+///
+/// Accepts RFC3339 (`2024-05-01T12:00:00Z`), a plain date (`2024-05-01`, UTC)
+/// or a raw unix timestamp. For plain dates, `end_of_day` selects 23:59:59
+/// instead of midnight so the date can be used as an inclusive upper bound.
+pub fn parse_date(input: &str, end_of_day: bool) -> Result<i64> {
+    if let Ok(ts) = input.parse::<i64>() {
+        return Ok(ts);
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.timestamp());
+    }
+    let date = NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid date '{}' (expected YYYY-MM-DD, RFC3339 or unix timestamp)",
+            input
+        )
+    })?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.unwrap_or_default().and_utc().timestamp())
+}
+
+/// Format a signed raw token amount with the given number of decimals.
+///
+/// Works on integers throughout so large balances keep full precision.
+pub fn format_amount(raw: i128, decimals: u8) -> String {
+    let sign = if raw < 0 { "-" } else { "" };
+    let digits = raw.unsigned_abs().to_string();
+    if decimals == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let decimals = decimals as usize;
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, frac) = padded.split_at(padded.len() - decimals);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        format!("{}{}", sign, whole)
+    } else {
+        format!("{}{}.{}", sign, whole, frac)
+    }
+}
+
+/// Format a signed lamport amount as SOL.
+pub fn format_sol(lamports: i128) -> String {
+    format_amount(lamports, 9)
+}
+
+/// Render bytes as an xxd-style hexdump.
+///
+/// Each line shows the absolute offset (starting at `base`), sixteen bytes
+/// in pairs and their printable ASCII characters.
+pub fn hexdump(bytes: &[u8], base: usize) -> String {
+    let mut out = String::new();
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = chunk
+            .chunks(2)
+            .map(|pair| pair.iter().map(|b| format!("{:02x}", b)).collect())
+            .collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        out.push_str(&format!(
+            "{:08x}: {:<40} {}\n",
+            base + i * 16,
+            hex.join(" "),
+            ascii
+        ));
+    }
+    out
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_utc(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Format a duration in seconds as `1d 02h 03m`, `2h 03m 04s` or `3m 04s`.
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    let (d, h, m, s) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if d > 0 {
+        format!("{}d {:02}h {:02}m", d, h, m)
+    } else if h > 0 {
+        format!("{}h {:02}m {:02}s", h, m, s)
+    } else {
+        format!("{}m {:02}s", m, s)
+    }
+}
+
+/// `part` as a percentage of `total` (0 when `total` is 0).
+pub fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
+}